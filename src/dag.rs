@@ -0,0 +1,246 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use serde::Serialize;
+
+use crate::job::{schedule_with_precedence, JobList};
+use crate::Task;
+
+#[derive(Debug)]
+pub enum DagError {
+    DuplicateNode(usize),
+    UnknownNode(usize),
+    Cycle,
+}
+
+/// a task made of subtasks with precedence constraints between them.
+/// all subtasks share the period and the end-to-end deadline of the dag
+#[derive(Clone, Debug)]
+pub struct DagTask {
+    id: usize,
+    period: usize,
+    deadline: usize,
+    offset: usize,
+    /// (task id, wcet) of every subtask
+    nodes: Vec<(usize, usize)>,
+    /// (predecessor, successor) task ids
+    edges: Vec<(usize, usize)>,
+}
+
+impl DagTask {
+    /// creates a dag task with an implicit end-to-end deadline.
+    /// subtask ids must be unique among all the tasks of the system
+    pub fn new(
+        id: usize,
+        period: usize,
+        nodes: Vec<(usize, usize)>,
+        edges: Vec<(usize, usize)>,
+    ) -> Result<Self, DagError> {
+        let mut ids = HashSet::new();
+        for (node, _) in &nodes {
+            if !ids.insert(*node) {
+                return Err(DagError::DuplicateNode(*node));
+            }
+        }
+        for node in edges.iter().flat_map(|(from, to)| [from, to]) {
+            if !ids.contains(node) {
+                return Err(DagError::UnknownNode(*node));
+            }
+        }
+
+        // Kahn's algorithm, every node gets visited iff there is no cycle
+        let mut in_degree: HashMap<usize, usize> = ids.iter().map(|n| (*n, 0)).collect();
+        for (_, to) in &edges {
+            *in_degree.get_mut(to).unwrap() += 1;
+        }
+        let mut sources: VecDeque<usize> = in_degree
+            .iter()
+            .filter(|(_, d)| **d == 0)
+            .map(|(n, _)| *n)
+            .collect();
+        let mut visited = 0;
+        while let Some(node) = sources.pop_front() {
+            visited += 1;
+            for (_, to) in edges.iter().filter(|(from, _)| *from == node) {
+                let degree = in_degree.get_mut(to).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    sources.push_back(*to);
+                }
+            }
+        }
+        if visited != nodes.len() {
+            return Err(DagError::Cycle);
+        }
+
+        Ok(Self {
+            id,
+            period,
+            deadline: period,
+            offset: 0,
+            nodes,
+            edges,
+        })
+    }
+
+    /// sets the relative end-to-end deadline of the dag
+    pub fn with_deadline(self, deadline: usize) -> Self {
+        Self { deadline, ..self }
+    }
+
+    /// sets the release time of the first instance of the dag
+    pub fn with_offset(self, offset: usize) -> Self {
+        Self { offset, ..self }
+    }
+
+    /// the subtasks as independent tasks, to be partitioned among processors.
+    /// each of them gets the end-to-end deadline of the dag
+    pub fn tasks(&self) -> Vec<Task> {
+        self.nodes
+            .iter()
+            .map(|(id, wcet)| {
+                Task::new(*id, *wcet, self.period)
+                    .with_deadline(self.deadline)
+                    .with_offset(self.offset)
+            })
+            .collect()
+    }
+
+    fn predecessors(&self, node: usize) -> Vec<usize> {
+        self.edges
+            .iter()
+            .filter(|(_, to)| *to == node)
+            .map(|(from, _)| *from)
+            .collect()
+    }
+}
+
+/// predecessors of every subtask of `dags`
+pub(crate) fn precedence(dags: &[DagTask]) -> HashMap<usize, Vec<usize>> {
+    dags.iter()
+        .flat_map(|dag| dag.nodes.iter().map(|(id, _)| (*id, dag.predecessors(*id))))
+        .collect()
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DagReport {
+    id: usize,
+    iteration: usize,
+    release_time: usize,
+    deadline: usize,
+    finish_time: Option<usize>,
+    response_time: Option<usize>,
+}
+
+/// simulates `joblists`, one per processor, up to `horizon` while respecting
+/// the precedence constraints of `dags`.
+/// a subtask is released once all of its predecessors are done, possibly on other
+/// processors. when subtasks are replicated, the first replica to finish counts.
+/// the pieces of split tasks run one after the other as well, even without dags.
+/// returns the end-to-end response time of every dag instance released before `horizon`
+pub fn schedule_dags(joblists: &mut [JobList], dags: &[DagTask], horizon: usize) -> Vec<DagReport> {
+    let completions = schedule_with_precedence(joblists, &precedence(dags), horizon);
+
+    let mut reports = Vec::new();
+    for dag in dags {
+        let mut iteration = 0;
+        let mut release_time = dag.offset;
+        while release_time < horizon {
+            let finish_time = dag
+                .nodes
+                .iter()
+                .map(|(id, _)| completions.get(&(*id, iteration)).copied())
+                .collect::<Option<Vec<usize>>>()
+                .and_then(|ends| ends.into_iter().max());
+            reports.push(DagReport {
+                id: dag.id,
+                iteration,
+                release_time,
+                deadline: release_time + dag.deadline,
+                finish_time,
+                response_time: finish_time.map(|f| f - release_time),
+            });
+            iteration += 1;
+            release_time += dag.period;
+        }
+    }
+    reports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DeadlineMiss, TaskList};
+
+    #[test]
+    fn cycle() {
+        let dag = DagTask::new(
+            1,
+            10,
+            vec![(1, 1), (2, 1), (3, 1)],
+            vec![(1, 2), (2, 3), (3, 2)],
+        );
+        assert!(matches!(dag, Err(DagError::Cycle)));
+        let dag = DagTask::new(1, 10, vec![(1, 1)], vec![(1, 2)]);
+        assert!(matches!(dag, Err(DagError::UnknownNode(2))));
+    }
+
+    #[test]
+    fn precedence_across_cpus() {
+        // 1 -> 2 and 1 -> 3, with 2 on another processor
+        let dag = DagTask::new(1, 10, vec![(1, 2), (2, 3), (3, 1)], vec![(1, 2), (1, 3)]).unwrap();
+        let tasks = dag.tasks();
+        let mut joblists = vec![
+            TaskList::from(vec![tasks[0].clone(), tasks[2].clone()]).jobs_till_hyperperiod(),
+            TaskList::from(vec![tasks[1].clone()]).jobs_till_hyperperiod(),
+        ];
+        let reports = schedule_dags(&mut joblists, &[dag], 10);
+
+        let mut timeline = vec![(0, 0); 10];
+        timeline[0..2].fill((1, 0));
+        timeline[2] = (3, 0);
+        assert_eq!(joblists[0].timeline(10), timeline);
+
+        let mut timeline = vec![(0, 0); 10];
+        timeline[2..5].fill((2, 0));
+        assert_eq!(joblists[1].timeline(10), timeline);
+
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].finish_time, Some(5));
+        assert_eq!(reports[0].response_time, Some(5));
+    }
+
+    #[test]
+    fn unfinished_instance() {
+        let dag = DagTask::new(1, 10, vec![(1, 4), (2, 4)], vec![(1, 2)])
+            .unwrap()
+            .with_deadline(20);
+        let mut joblists = vec![TaskList::from(dag.tasks()).jobs_till_hyperperiod()];
+        let reports = schedule_dags(&mut joblists, &[dag], 6);
+        assert_eq!(reports[0].finish_time, None);
+    }
+
+    #[test]
+    fn late_predecessor() {
+        // 1 completes after the end-to-end deadline, releasing 2 past its deadline
+        let dag = DagTask::new(1, 10, vec![(1, 12), (2, 1)], vec![(1, 2)]).unwrap();
+        let mut joblists = vec![TaskList::from(dag.tasks())
+            .jobs_till(10)
+            .with_deadline_miss(DeadlineMiss::ContinueLate)];
+        let reports = schedule_dags(&mut joblists, &[dag], 20);
+        assert_eq!(reports[0].finish_time, Some(13));
+    }
+
+    #[test]
+    fn schedule_with_dags() {
+        let dag = DagTask::new(1, 10, vec![(1, 2), (2, 3)], vec![(2, 1)]).unwrap();
+        let mut jobs = TaskList::from(dag.tasks())
+            .jobs_till_hyperperiod()
+            .with_dags(&[dag]);
+        jobs.schedule();
+        let mut timeline = vec![(0, 0); 10];
+        timeline[0..3].fill((2, 0));
+        timeline[3..5].fill((1, 0));
+        assert_eq!(jobs.timeline(10), timeline);
+    }
+}
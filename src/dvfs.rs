@@ -0,0 +1,166 @@
+use serde::Serialize;
+
+/// a frequency the processor can run at and the voltage it needs for it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OperatingPoint {
+    /// in MHz
+    frequency: usize,
+    /// in volts
+    voltage: f64,
+}
+
+impl OperatingPoint {
+    pub fn new(frequency: usize, voltage: f64) -> Self {
+        assert!(frequency > 0, "frequency has to be positive");
+        Self { frequency, voltage }
+    }
+
+    pub fn frequency(&self) -> usize {
+        self.frequency
+    }
+}
+
+/// power drawn by a processor, `static_power + capacitance * voltage^2 * frequency`
+/// while busy and `static_power` while idle
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PowerModel {
+    capacitance: f64,
+    static_power: f64,
+}
+
+impl Default for PowerModel {
+    fn default() -> Self {
+        Self {
+            capacitance: 1.0,
+            static_power: 0.0,
+        }
+    }
+}
+
+impl PowerModel {
+    pub fn new(capacitance: f64) -> Self {
+        Self {
+            capacitance,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_static_power(self, static_power: f64) -> Self {
+        Self {
+            static_power,
+            ..self
+        }
+    }
+
+    fn busy_power(&self, point: &OperatingPoint) -> f64 {
+        self.static_power + self.capacitance * point.voltage.powi(2) * point.frequency as f64
+    }
+}
+
+/// how the frequency of a processor is picked
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Governor {
+    /// always the highest frequency
+    #[default]
+    Maximum,
+    /// the lowest frequency that keeps the jobs schedulable under EDF,
+    /// picked once from the density of the tasks
+    StaticSlowdown,
+    /// cycle-conserving EDF: a task accounts for its wcet from the release of
+    /// a job and for the execution the job actually took once it completes,
+    /// the frequency is the lowest one covering the sum of these utilizations
+    CycleConserving,
+}
+
+/// operating points of a processor along with its power model
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dvfs {
+    /// frequency ascending
+    points: Vec<OperatingPoint>,
+    power: PowerModel,
+    governor: Governor,
+}
+
+impl Dvfs {
+    pub fn new(mut points: Vec<OperatingPoint>) -> Self {
+        assert!(!points.is_empty(), "no operating points");
+        points.sort_by_key(|p| p.frequency);
+        Self {
+            points,
+            power: PowerModel::default(),
+            governor: Governor::default(),
+        }
+    }
+
+    pub fn with_power_model(self, power: PowerModel) -> Self {
+        Self { power, ..self }
+    }
+
+    pub fn with_governor(self, governor: Governor) -> Self {
+        Self { governor, ..self }
+    }
+
+    pub(crate) fn governor(&self) -> Governor {
+        self.governor
+    }
+
+    pub fn max_frequency(&self) -> usize {
+        self.points.last().unwrap().frequency
+    }
+
+    /// the lowest frequency that provides `utilization` of the highest one,
+    /// the highest frequency if none does
+    pub fn lowest_frequency(&self, utilization: f64) -> usize {
+        let max = self.max_frequency() as f64;
+        self.points
+            .iter()
+            .map(|p| p.frequency)
+            .find(|f| *f as f64 >= utilization * max - 1e-9)
+            .unwrap_or(self.max_frequency())
+    }
+
+    /// energy spent till `to`, busy at the given frequency during
+    /// each of `busy` and idle otherwise
+    pub(crate) fn energy(&self, busy: &[FrequencySegment], to: usize) -> f64 {
+        let mut idle = to;
+        let mut energy = 0.0;
+        for segment in busy.iter().filter(|s| s.from < to) {
+            let duration = segment.to.min(to) - segment.from;
+            let point = self
+                .points
+                .iter()
+                .find(|p| p.frequency == segment.frequency)
+                .unwrap();
+            energy += self.power.busy_power(point) * duration as f64;
+            idle -= duration;
+        }
+        energy + self.power.static_power * idle as f64
+    }
+}
+
+/// the processor was busy at `frequency` during `from..to`
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct FrequencySegment {
+    pub(crate) from: usize,
+    pub(crate) to: usize,
+    pub(crate) frequency: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lowest_frequency() {
+        let dvfs = Dvfs::new(vec![
+            OperatingPoint::new(1000, 1.2),
+            OperatingPoint::new(250, 0.8),
+            OperatingPoint::new(500, 1.0),
+        ]);
+        assert_eq!(dvfs.lowest_frequency(0.1), 250);
+        assert_eq!(dvfs.lowest_frequency(0.25), 250);
+        assert_eq!(dvfs.lowest_frequency(0.3), 500);
+        assert_eq!(dvfs.lowest_frequency(1.5), 1000);
+    }
+}
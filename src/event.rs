@@ -0,0 +1,187 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+use crate::job::Job;
+use crate::task::Criticality;
+
+/// something the scheduler of a processor reacts to
+pub(crate) enum Event {
+    /// the job gets released
+    Arrival(Job),
+    /// the job left the processor, completed or aborted
+    Completion(Job),
+    /// the deadline of the job passed, it's aborted if it's still waiting
+    Deadline { id: usize, iteration: usize },
+    /// the job that ran last used up its LO budget without completing
+    BudgetExhaustion,
+    /// the server with the id used up its budget
+    ServerExhaustion { server: usize },
+    /// the server with the id gets budget back
+    Replenishment { server: usize },
+    /// the processor switches to the mode
+    ModeChange(Criticality),
+}
+
+const ARRIVAL_RANK: usize = 3;
+
+impl Event {
+    /// order in which the events of the same time are handled
+    fn rank(&self) -> usize {
+        match self {
+            Event::BudgetExhaustion | Event::ServerExhaustion { .. } => 0,
+            Event::ModeChange(_) => 1,
+            Event::Completion(_) => 2,
+            Event::Arrival(_) => ARRIVAL_RANK,
+            // a polling server keeps its budget for the requests arriving along
+            Event::Replenishment { .. } => 4,
+            Event::Deadline { .. } => 5,
+        }
+    }
+
+    /// whether the event is only handled at the next scheduling decision,
+    /// instead of ending the execution segment in progress
+    fn is_deferred(&self) -> bool {
+        matches!(self, Event::Deadline { .. })
+    }
+}
+
+struct Entry {
+    time: usize,
+    rank: usize,
+    /// events of the same time and rank are handled in the order they were pushed
+    seq: usize,
+    event: Event,
+}
+
+impl Entry {
+    fn key(&self) -> (usize, usize, usize) {
+        (self.time, self.rank, self.seq)
+    }
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// the events of a processor, earliest first
+#[derive(Default)]
+pub(crate) struct EventQueue {
+    events: BinaryHeap<Reverse<Entry>>,
+    /// deadlines stay here after their job leaves the processor,
+    /// and are dropped once due since there's no job left to abort
+    deferred: BinaryHeap<Reverse<Entry>>,
+    seq: usize,
+}
+
+impl EventQueue {
+    pub(crate) fn push(&mut self, time: usize, event: Event) {
+        let entry = Entry {
+            time,
+            rank: event.rank(),
+            seq: self.seq,
+            event,
+        };
+        self.seq += 1;
+        match entry.event.is_deferred() {
+            true => self.deferred.push(Reverse(entry)),
+            false => self.events.push(Reverse(entry)),
+        }
+    }
+
+    /// time of the next event that isn't deferred
+    pub(crate) fn next_time(&self) -> Option<usize> {
+        self.events.peek().map(|Reverse(e)| e.time)
+    }
+
+    /// the next event that isn't deferred, if it's due by `now`
+    pub(crate) fn pop(&mut self, now: usize) -> Option<Event> {
+        Self::pop_before(&mut self.events, (now, usize::MAX))
+    }
+
+    /// the next event that isn't deferred, if it's due by `now`
+    /// and handled before the arrivals at `now`
+    pub(crate) fn pop_before_arrivals(&mut self, now: usize) -> Option<Event> {
+        Self::pop_before(&mut self.events, (now, ARRIVAL_RANK))
+    }
+
+    /// the next deferred event, if it's due by `now`
+    pub(crate) fn pop_deferred(&mut self, now: usize) -> Option<Event> {
+        Self::pop_before(&mut self.deferred, (now, usize::MAX))
+    }
+
+    /// the next event of `heap`, if it comes before `(time, rank)`
+    fn pop_before(heap: &mut BinaryHeap<Reverse<Entry>>, limit: (usize, usize)) -> Option<Event> {
+        heap.peek()
+            .is_some_and(|Reverse(e)| (e.time, e.rank) < limit)
+            .then(|| heap.pop().unwrap().0.event)
+    }
+
+    /// removes the events matching `predicate` that aren't deferred, in order
+    pub(crate) fn take(&mut self, predicate: impl Fn(&Event) -> bool) -> Vec<Event> {
+        let (mut taken, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.events)
+            .into_vec()
+            .into_iter()
+            .partition(|Reverse(e)| predicate(&e.event));
+        self.events = kept.into();
+        taken.sort_by_key(|Reverse(e)| e.key());
+        taken.into_iter().map(|Reverse(e)| e.event).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn order() {
+        let job = |id| Job::new(id, 0, 0, 1, 10);
+        let mut events = EventQueue::default();
+        events.push(5, Event::Replenishment { server: 9 });
+        events.push(5, Event::Arrival(job(1)));
+        events.push(
+            3,
+            Event::Deadline {
+                id: 2,
+                iteration: 0,
+            },
+        );
+        events.push(5, Event::Completion(job(3)));
+        events.push(4, Event::Arrival(job(4)));
+
+        // deadlines don't stop the execution
+        assert_eq!(events.next_time(), Some(4));
+        assert!(events.pop(3).is_none());
+        assert!(matches!(events.pop(5), Some(Event::Arrival(j)) if j.id() == 4));
+        assert!(matches!(
+            events.pop_before_arrivals(5),
+            Some(Event::Completion(_))
+        ));
+        assert!(events.pop_before_arrivals(5).is_none());
+        assert!(matches!(events.pop(5), Some(Event::Arrival(_))));
+        // the server is replenished after the requests of the same time arrive
+        assert!(matches!(
+            events.pop(5),
+            Some(Event::Replenishment { server: 9 })
+        ));
+        assert!(matches!(
+            events.pop_deferred(5),
+            Some(Event::Deadline { id: 2, .. })
+        ));
+    }
+}
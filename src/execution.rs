@@ -0,0 +1,123 @@
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+
+/// distribution of the actual execution time of the jobs of a task.
+/// every draw lies in `bcet..=wcet` of the task
+#[derive(Clone, Debug)]
+pub enum ExecutionTime {
+    /// every job runs for exactly the wcet of its task
+    Wcet,
+    /// uniformly distributed in `bcet..=wcet`
+    Uniform { bcet: usize },
+    /// normal distribution truncated to `bcet..=wcet`
+    TruncatedNormal {
+        bcet: usize,
+        mean: f64,
+        std_dev: f64,
+    },
+    /// empirical histogram of `(execution time, weight)` pairs
+    Histogram(Vec<(usize, f64)>),
+}
+
+impl ExecutionTime {
+    /// panics if the distribution can't produce a value in `..=wcet`
+    pub(crate) fn validate(&self, wcet: usize) {
+        match self {
+            ExecutionTime::Wcet => {}
+            ExecutionTime::Uniform { bcet } => assert!(*bcet <= wcet, "bcet is larger than wcet"),
+            ExecutionTime::TruncatedNormal {
+                bcet,
+                mean,
+                std_dev,
+            } => {
+                assert!(*bcet <= wcet, "bcet is larger than wcet");
+                assert!(
+                    *bcet as f64 <= *mean && *mean <= wcet as f64,
+                    "mean is out of bcet..=wcet"
+                );
+                assert!(*std_dev >= 0.0, "negative standard deviation");
+            }
+            ExecutionTime::Histogram(bins) => {
+                assert!(
+                    bins.iter().all(|(e, _)| *e <= wcet),
+                    "histogram has an execution time larger than wcet"
+                );
+                assert!(
+                    WeightedIndex::new(bins.iter().map(|(_, w)| *w)).is_ok(),
+                    "invalid histogram weights"
+                );
+            }
+        }
+    }
+
+    pub(crate) fn sample<R: Rng>(&self, wcet: usize, rng: &mut R) -> usize {
+        match self {
+            ExecutionTime::Wcet => wcet,
+            ExecutionTime::Uniform { bcet } => rng.gen_range(*bcet..=wcet),
+            ExecutionTime::TruncatedNormal {
+                bcet,
+                mean,
+                std_dev,
+            } => loop {
+                // Box-Muller transform, rejecting draws outside of bcet..=wcet
+                let u1 = 1.0 - rng.gen::<f64>();
+                let u2 = rng.gen::<f64>();
+                let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                let execution = (mean + std_dev * z).round();
+                if *bcet as f64 <= execution && execution <= wcet as f64 {
+                    break execution as usize;
+                }
+            },
+            ExecutionTime::Histogram(bins) => {
+                let index = WeightedIndex::new(bins.iter().map(|(_, w)| *w)).unwrap();
+                bins[index.sample(rng)].0
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn samples_in_range() {
+        const TEST_SIZE: usize = 1000;
+        let mut rng = StdRng::seed_from_u64(0);
+        let distributions = [
+            ExecutionTime::Wcet,
+            ExecutionTime::Uniform { bcet: 4 },
+            ExecutionTime::TruncatedNormal {
+                bcet: 4,
+                mean: 6.0,
+                std_dev: 3.0,
+            },
+            ExecutionTime::Histogram(vec![(4, 1.0), (7, 3.0)]),
+        ];
+        for distribution in distributions {
+            distribution.validate(10);
+            for _ in 0..TEST_SIZE {
+                let execution = distribution.sample(10, &mut rng);
+                assert!((4..=10).contains(&execution));
+            }
+        }
+    }
+
+    #[test]
+    fn histogram_values() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let histogram = ExecutionTime::Histogram(vec![(4, 1.0), (7, 3.0), (9, 0.0)]);
+        for _ in 0..100 {
+            assert!([4, 7].contains(&histogram.sample(10, &mut rng)));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn bcet_gt_wcet() {
+        ExecutionTime::Uniform { bcet: 11 }.validate(10);
+    }
+}
@@ -0,0 +1,374 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::job::{Job, JobReport};
+use crate::platform::Platform;
+use crate::task::TaskList;
+
+/// a job of the global schedule and the processors it ran on
+struct GlobalJob {
+    job: Job,
+    /// index of the replica of the task the job belongs to
+    replica: usize,
+    /// (processor, start, end) of every execution segment
+    segments: Vec<(usize, usize, usize)>,
+}
+
+impl GlobalJob {
+    fn key(&self) -> (usize, usize) {
+        (self.job.id(), self.job.iteration())
+    }
+
+    fn slot(&self) -> (usize, usize, usize) {
+        (self.job.id(), self.job.iteration(), self.replica)
+    }
+
+    fn last_cpu(&self) -> Option<usize> {
+        self.segments.last().map(|s| s.0)
+    }
+
+    fn run(&mut self, cpu: usize, from: usize, to: usize) {
+        let end = from + self.job.run(from, to);
+        match self.segments.last_mut() {
+            Some(last) if last.0 == cpu && last.2 == from => last.2 = end,
+            _ => self.segments.push((cpu, from, end)),
+        }
+    }
+
+    fn migrations(&self) -> usize {
+        self.segments
+            .windows(2)
+            .filter(|w| w[0].0 != w[1].0)
+            .count()
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct GlobalJobReport {
+    #[serde(flatten)]
+    job: JobReport,
+    replica: usize,
+    /// number of times the job resumed on another processor
+    migrations: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct GlobalReport {
+    jobs: Vec<GlobalJobReport>,
+    deadline_misses: usize,
+    /// (id, iteration, replica) of the job running in every slot of every processor
+    timelines: Vec<Vec<(usize, usize, usize)>>,
+}
+
+impl GlobalReport {
+    pub fn meets_all_deadlines(&self) -> bool {
+        self.deadline_misses == 0
+    }
+}
+
+/// (replica, processor) pairs every job ran on so far, by (id, iteration)
+type Placements = HashMap<(usize, usize), HashSet<(usize, usize)>>;
+
+/// the processors `job` may run on. a replica never runs on a processor another
+/// replica of the job ran on, so that a failing processor takes out at most one of them
+fn allowed_cpus(job: &GlobalJob, placements: &Placements, num_proc: usize) -> Vec<bool> {
+    let mut allowed = vec![true; num_proc];
+    for (replica, cpu) in placements.get(&job.key()).into_iter().flatten() {
+        if *replica != job.replica {
+            allowed[*cpu] = false;
+        }
+    }
+    allowed
+}
+
+/// the processor every job of `ready` runs on, by index in `ready`.
+/// jobs are picked by earliest deadline as long as a processor they may run on
+/// is left, so no processor idles while a job could run on it.
+/// the picked jobs stay on their processor when they can, to avoid needless migrations
+fn assign(ready: &[GlobalJob], placements: &Placements, num_proc: usize) -> Vec<Option<usize>> {
+    let allowed: Vec<Vec<bool>> = ready
+        .iter()
+        .map(|j| allowed_cpus(j, placements, num_proc))
+        .collect();
+    let mut greedy: Vec<Option<usize>> = vec![None; num_proc];
+    let mut picked = Vec::new();
+    for (i, allowed) in allowed.iter().enumerate() {
+        if picked.len() == num_proc {
+            break;
+        }
+        if let Some(cpu) = (0..num_proc).find(|c| greedy[*c].is_none() && allowed[*c]) {
+            greedy[cpu] = Some(i);
+            picked.push(i);
+        }
+    }
+
+    let mut assigned: Vec<Option<usize>> = vec![None; num_proc];
+    for &i in &picked {
+        if let Some(cpu) = ready[i].last_cpu() {
+            if assigned[cpu].is_none() && allowed[i][cpu] {
+                assigned[cpu] = Some(i);
+            }
+        }
+    }
+    for &i in &picked {
+        if assigned.contains(&Some(i)) {
+            continue;
+        }
+        match (0..num_proc).find(|c| assigned[*c].is_none() && allowed[i][*c]) {
+            Some(cpu) => assigned[cpu] = Some(i),
+            // keeping jobs on their processor left this one out
+            None => return greedy,
+        }
+    }
+    assigned
+}
+
+/// simulates global EDF on `num_proc` processors over the feasibility interval
+/// of `tasklist`: at any time the (at most) `num_proc` ready jobs with the
+/// earliest deadlines that can run somewhere run, migrating between processors as needed.
+/// every task gets `replication + 1` replicas, like in partitioning, and the replicas
+/// of a job run on distinct processors. a replica left without a processor misses its deadline.
+/// jobs keep their processor when they can, to avoid needless migrations
+pub fn schedule_global(tasklist: &TaskList, num_proc: usize) -> GlobalReport {
+    schedule_global_on(tasklist, &Platform::identical(num_proc))
+}
+
+/// global EDF on the processors of `platform`,
+/// the jobs run at the speed of the processor they are on
+pub fn schedule_global_on(tasklist: &TaskList, platform: &Platform) -> GlobalReport {
+    let num_proc = platform.num_proc();
+    let mut pending = Vec::new();
+    for replica in 0..=tasklist.replication() {
+        let mut joblist = tasklist.jobs_till_hyperperiod();
+        while let Some(job) = joblist.pop() {
+            pending.push(GlobalJob {
+                job,
+                replica,
+                segments: Vec::new(),
+            });
+        }
+    }
+    // arrival time decending
+    pending.sort_by_key(|j| std::cmp::Reverse(j.job.arrival_time()));
+
+    let mut ready: Vec<GlobalJob> = Vec::new();
+    let mut finished: Vec<GlobalJob> = Vec::new();
+    let mut placements = Placements::new();
+    let mut now = 0;
+    loop {
+        while pending.last().is_some_and(|j| j.job.arrival_time() <= now) {
+            ready.push(pending.pop().unwrap());
+        }
+        let (expired, mut waiting): (Vec<GlobalJob>, Vec<GlobalJob>) =
+            ready.into_iter().partition(|j| j.job.deadline() <= now);
+        finished.extend(expired.into_iter().map(|j| GlobalJob {
+            job: j.job.expired(),
+            ..j
+        }));
+        waiting.sort_by_key(|j| (j.job.deadline(), j.job.id(), j.replica));
+        ready = waiting;
+
+        let assigned = assign(&ready, &placements, num_proc);
+        for (cpu, i) in assigned.iter().enumerate() {
+            if let Some(i) = i {
+                let job = &mut ready[*i].job;
+                job.migrate(platform.speed(job.id(), cpu));
+            }
+        }
+        let next_arrival = pending.last().map(|j| j.job.arrival_time());
+        let until = assigned
+            .iter()
+            .flatten()
+            .map(|i| {
+                ready[*i]
+                    .job
+                    .run_end(now, next_arrival.unwrap_or(usize::MAX))
+            })
+            .chain(next_arrival)
+            // the jobs that can't run anywhere wait for their deadlines
+            .chain(
+                (0..ready.len())
+                    .filter(|i| !assigned.contains(&Some(*i)))
+                    .map(|i| ready[i].job.deadline()),
+            )
+            .min();
+        // every job left either runs or waits for its deadline
+        let Some(until) = until else {
+            break;
+        };
+        for (cpu, i) in assigned.iter().enumerate() {
+            if let Some(i) = i {
+                ready[*i].run(cpu, now, until);
+                placements
+                    .entry(ready[*i].key())
+                    .or_default()
+                    .insert((ready[*i].replica, cpu));
+            }
+        }
+        now = until;
+        let (done, running): (Vec<GlobalJob>, Vec<GlobalJob>) =
+            ready.into_iter().partition(|j| j.job.is_finished());
+        finished.extend(done);
+        ready = running;
+    }
+
+    let end = finished
+        .iter()
+        .flat_map(|j| j.segments.iter().map(|s| s.2))
+        .max()
+        .unwrap_or(0)
+        .max(tasklist.feasibility_interval());
+    let mut timelines = vec![vec![(0, 0, 0); end]; num_proc];
+    for job in &finished {
+        for (cpu, start, end) in &job.segments {
+            for slot in &mut timelines[*cpu][*start..*end] {
+                *slot = job.slot();
+            }
+        }
+    }
+    finished.sort_by_key(|j| (j.job.arrival_time(), j.job.id(), j.replica));
+    GlobalReport {
+        deadline_misses: finished.iter().filter(|j| j.job.missed()).count(),
+        jobs: finished
+            .iter()
+            .map(|j| GlobalJobReport {
+                job: JobReport::from(&j.job),
+                replica: j.replica,
+                migrations: j.migrations(),
+            })
+            .collect(),
+        timelines,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Task;
+
+    #[test]
+    fn fails_to_partition() {
+        let t1 = Task::new(1, 3, 5);
+        let t2 = Task::new(2, 3, 5);
+        let t3 = Task::new(3, 1, 2);
+        let tasklist = TaskList::from(vec![t1, t2, t3]);
+        assert!(tasklist.first_fit(2).is_err());
+
+        let report = schedule_global(&tasklist, 2);
+        assert!(report.meets_all_deadlines());
+        assert_eq!(report.jobs.len(), 9);
+        assert!(!tasklist.is_global_edf_schedulable(2));
+        assert!(TaskList::from(vec![Task::new(1, 1, 2), Task::new(2, 3, 5)])
+            .is_global_edf_schedulable(2));
+    }
+
+    #[test]
+    fn migration() {
+        let t1 = Task::new(1, 2, 10);
+        let t2 = Task::new(2, 3, 10);
+        let t3 = Task::new(3, 2, 10).with_deadline(2).with_offset(1);
+        let tasklist = TaskList::from(vec![t1, t2, t3]);
+        let report = schedule_global(&tasklist, 2);
+        assert!(report.meets_all_deadlines());
+        assert_eq!(
+            report.timelines[0][..4],
+            [(1, 0, 0), (1, 0, 0), (2, 0, 0), (2, 0, 0)]
+        );
+        assert_eq!(
+            report.timelines[1][..4],
+            [(2, 0, 0), (3, 0, 0), (3, 0, 0), (0, 0, 0)]
+        );
+        let migrations: Vec<(usize, usize)> = report
+            .jobs
+            .iter()
+            .filter(|j| j.migrations != 0)
+            .map(|j| (j.job.id, j.migrations))
+            .collect();
+        assert_eq!(migrations, vec![(2, 1), (2, 1)]);
+    }
+
+    #[test]
+    fn replicas_never_share_a_processor() {
+        let tasklist = TaskList::from(vec![Task::new(1, 2, 4)]).with_replication(1);
+        let report = schedule_global(&tasklist, 2);
+        assert!(report.meets_all_deadlines());
+        assert_eq!(
+            report.timelines[0][..4],
+            [(1, 0, 0), (1, 0, 0), (0, 0, 0), (0, 0, 0)]
+        );
+        assert_eq!(
+            report.timelines[1][..4],
+            [(1, 0, 1), (1, 0, 1), (0, 0, 0), (0, 0, 0)]
+        );
+        // with a single processor the second replica never runs
+        let report = schedule_global(&tasklist, 1);
+        assert_eq!(report.deadline_misses, 1);
+        assert_eq!(
+            report.timelines[0][..4],
+            [(1, 0, 0), (1, 0, 0), (0, 0, 0), (0, 0, 0)]
+        );
+
+        // the replicas of t1 preempted by the ones of t2 resume on their own processors
+        let tasklist = TaskList::from(vec![
+            Task::new(1, 2, 10).with_deadline(5),
+            Task::new(2, 2, 10).with_offset(1).with_deadline(2),
+        ])
+        .with_replication(1);
+        let report = schedule_global(&tasklist, 2);
+        assert!(report.meets_all_deadlines());
+        assert_eq!(
+            report.timelines[0][..5],
+            [(1, 0, 0), (2, 0, 0), (2, 0, 0), (1, 0, 0), (0, 0, 0)]
+        );
+        assert_eq!(
+            report.timelines[1][..5],
+            [(1, 0, 1), (2, 0, 1), (2, 0, 1), (1, 0, 1), (0, 0, 0)]
+        );
+    }
+
+    #[test]
+    fn heterogeneous() {
+        let tasklist = TaskList::from(vec![
+            Task::new(1, 2, 10),
+            Task::new(2, 2, 10).with_deadline(9),
+        ]);
+        let report = schedule_global_on(&tasklist, &Platform::uniform(vec![1.0, 0.5]));
+        assert!(report.meets_all_deadlines());
+        assert_eq!(report.timelines[0][..3], [(2, 0, 0), (2, 0, 0), (0, 0, 0)]);
+        // t1 takes twice as long on the slower processor
+        assert_eq!(
+            report.timelines[1][..5],
+            [(1, 0, 0), (1, 0, 0), (1, 0, 0), (1, 0, 0), (0, 0, 0)]
+        );
+    }
+
+    #[test]
+    fn work_conserving() {
+        // replica 1 of tasks 1 and 2 can't use processor 0, where replica 0 ran
+        let job = |id, replica, last_cpu: Option<usize>| GlobalJob {
+            job: Job::new(id, 0, 0, 2, 10),
+            replica,
+            segments: last_cpu.map(|cpu| (cpu, 0, 1)).into_iter().collect(),
+        };
+        let ready = vec![
+            job(1, 1, None),
+            job(2, 1, None),
+            job(1, 0, Some(0)),
+            job(2, 0, Some(0)),
+        ];
+        let mut placements = Placements::new();
+        for job in &ready {
+            for (cpu, _, _) in &job.segments {
+                placements
+                    .entry(job.key())
+                    .or_default()
+                    .insert((job.replica, *cpu));
+            }
+        }
+        // processor 0 goes to the next job that can run on it
+        assert_eq!(assign(&ready, &placements, 2), vec![Some(2), Some(0)]);
+    }
+}
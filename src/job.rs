@@ -1,13 +1,84 @@
-use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::dag::{precedence, DagTask};
+use crate::dvfs::{Dvfs, FrequencySegment, Governor};
+use crate::event::{Event, EventQueue};
+use crate::metadata::{Metadata, Selector};
+use crate::overhead::{Overhead, OverheadKind, Overheads};
+use crate::platform::{Platform, Speed};
+use crate::policy::{Edf, SchedulingPolicy};
+use crate::ready::ReadyQueue;
+use crate::server::{Server, ServerKind};
+use crate::task::Criticality;
+use crate::time::{Resolution, TimeError, TimeUnit};
 
 pub struct Job {
     id: usize,
     iteration: usize,
     arrival_time: usize,
     deadline: usize,
+    /// deadline used to prioritize the job, it differs from `deadline`
+    /// only for HI jobs in the LO mode of EDF-VD
+    virtual_deadline: usize,
+    period: usize,
+    /// fixed priority, 0 being the highest
+    priority: usize,
+    criticality: Criticality,
+    /// execution budget, the LO budget for HI jobs
+    wcet: usize,
     remaining: usize,
+    /// execution done so far, in ticks at the reference speed
+    executed: usize,
+    /// cycles executed towards the next tick of `executed`
+    carry: usize,
+    /// speed of the processor the job runs on, relative to the one its wcet is given for
+    speed: Speed,
     log: Vec<(usize, usize)>,
     status: JobStatus,
+    kind: JobKind,
+    /// relative deadline of the task of the job
+    relative_deadline: usize,
+    /// relative deadline of the task, a smaller value means a higher preemption level
+    preemption_level: usize,
+    critical_sections: Vec<CriticalSection>,
+    non_preemptive_region: Option<usize>,
+    /// metadata of the task, shared by all of its jobs
+    metadata: Rc<Metadata>,
+    /// intervals during which the job was kept from running by a lower priority job
+    blocked: Vec<(usize, usize)>,
+    /// number of times the job was stopped to run another one before it finished
+    preemptions: usize,
+    deadline_miss: DeadlineMiss,
+    /// time from the arrival of the job to the timer tick that released it,
+    /// under tick-based scheduling
+    release_latency: Option<usize>,
+    /// time the last of its predecessors completed, for a job with precedence constraints
+    precedence_release: Option<usize>,
+    /// (index, count) of the piece, for a job of a task split across processors
+    piece: Option<(usize, usize)>,
+}
+
+/// a part of the execution of a job that holds an exclusive resource
+#[derive(Clone, Debug)]
+pub(crate) struct CriticalSection {
+    pub(crate) resource: usize,
+    /// execution time of the job before entering the section
+    pub(crate) offset: usize,
+    pub(crate) length: usize,
+}
+
+#[derive(Clone, Copy)]
+enum JobKind {
+    Periodic,
+    /// one-shot job served in background, or by `server` if set.
+    /// missing its soft deadline doesn't abort it
+    Aperiodic {
+        soft_deadline: Option<usize>,
+        server: Option<usize>,
+    },
 }
 
 #[derive(Serialize, Clone)]
@@ -17,6 +88,13 @@ enum JobStatus {
     Running,
     DeadlineExceeded,
     Done,
+    /// discarded LO job in the HI mode of EDF-VD
+    Dropped,
+    /// not run since the previous job of its task missed its deadline,
+    /// see `DeadlineMiss::SkipNext`
+    Skipped,
+    /// still waiting to run or complete at the end of the simulation
+    Unfinished,
 }
 
 impl Job {
@@ -32,21 +110,285 @@ impl Job {
             iteration,
             arrival_time,
             deadline,
+            virtual_deadline: deadline,
+            period: deadline - arrival_time,
+            priority: usize::MAX,
+            criticality: Criticality::Lo,
+            wcet,
             remaining: wcet,
+            executed: 0,
+            carry: 0,
+            speed: Speed::FULL,
             log: Vec::new(),
             status: JobStatus::Ready,
+            kind: JobKind::Periodic,
+            relative_deadline: deadline - arrival_time,
+            preemption_level: deadline - arrival_time,
+            critical_sections: Vec::new(),
+            non_preemptive_region: None,
+            metadata: Rc::default(),
+            blocked: Vec::new(),
+            preemptions: 0,
+            deadline_miss: DeadlineMiss::default(),
+            release_latency: None,
+            precedence_release: None,
+            piece: None,
+        }
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn iteration(&self) -> usize {
+        self.iteration
+    }
+
+    pub fn arrival_time(&self) -> usize {
+        self.arrival_time
+    }
+
+    pub fn deadline(&self) -> usize {
+        self.deadline
+    }
+
+    /// absolute deadline the job is scheduled by.
+    /// it's the virtual deadline for HI jobs in the LO mode of EDF-VD
+    pub fn scheduling_deadline(&self) -> usize {
+        self.virtual_deadline
+    }
+
+    /// relative deadline of the task of the job
+    pub fn relative_deadline(&self) -> usize {
+        self.relative_deadline
+    }
+
+    /// period of the task of the job, defaults to its relative deadline
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// execution left to the job before it exhausts its wcet
+    pub fn remaining_wcet(&self) -> usize {
+        self.wcet.saturating_sub(self.consumed())
+    }
+
+    /// fixed priority of the task of the job, 0 being the highest
+    pub fn priority(&self) -> usize {
+        self.priority
+    }
+
+    /// sets the actual execution demand of the job, which defaults to its wcet
+    pub(crate) fn with_execution(self, execution: usize) -> Self {
+        Self {
+            remaining: execution,
+            ..self
+        }
+    }
+
+    pub(crate) fn with_period(self, period: usize) -> Self {
+        Self { period, ..self }
+    }
+
+    pub(crate) fn with_priority(self, priority: usize) -> Self {
+        Self { priority, ..self }
+    }
+
+    pub(crate) fn with_criticality(self, criticality: Criticality) -> Self {
+        Self {
+            criticality,
+            ..self
+        }
+    }
+
+    pub(crate) fn with_critical_sections(self, critical_sections: Vec<CriticalSection>) -> Self {
+        Self {
+            critical_sections,
+            ..self
+        }
+    }
+
+    pub(crate) fn with_non_preemptive_region(self, length: Option<usize>) -> Self {
+        Self {
+            non_preemptive_region: length,
+            ..self
+        }
+    }
+
+    pub(crate) fn with_metadata(self, metadata: Rc<Metadata>) -> Self {
+        Self { metadata, ..self }
+    }
+
+    pub(crate) fn with_piece(self, piece: Option<(usize, usize)>) -> Self {
+        Self { piece, ..self }
+    }
+
+    fn dropped(self) -> Self {
+        Self {
+            status: JobStatus::Dropped,
+            ..self
+        }
+    }
+
+    /// aborts a job whose deadline passed before it could run
+    pub(crate) fn expired(self) -> Self {
+        Self {
+            status: JobStatus::DeadlineExceeded,
+            ..self
+        }
+    }
+
+    pub(crate) fn unfinished(self) -> Self {
+        Self {
+            status: JobStatus::Unfinished,
+            ..self
+        }
+    }
+
+    fn skipped(self) -> Self {
+        Self {
+            status: JobStatus::Skipped,
+            ..self
+        }
+    }
+
+    /// the time the job can be released at, once it arrived and its predecessors completed
+    fn release_time(&self) -> usize {
+        self.arrival_time.max(self.precedence_release.unwrap_or(0))
+    }
+
+    /// whether the job gets aborted for being still unfinished at `now`
+    pub(crate) fn expires_at(&self, now: usize) -> bool {
+        self.deadline_miss.aborts() && self.deadline <= now
+    }
+
+    /// how late the job completed, if it did
+    fn tardiness(&self) -> Option<usize> {
+        match (&self.status, self.log.last()) {
+            (JobStatus::Done, Some((_, end))) => Some(end.saturating_sub(self.deadline)),
+            _ => None,
+        }
+    }
+
+    fn consumed(&self) -> usize {
+        self.executed
+    }
+
+    /// density of the task of the job on its processor,
+    /// were the job to execute for `execution`
+    fn utilization(&self, execution: usize) -> f64 {
+        self.speed.time(execution) as f64 / self.relative_deadline.min(self.period) as f64
+    }
+
+    /// time the job takes to complete with its processor running at `speed`
+    fn time_left(&self, speed: Speed) -> usize {
+        self.time_for(self.remaining, speed)
+    }
+
+    /// time the job takes to execute for `execution` more
+    /// with its processor running at `speed`
+    fn time_for(&self, execution: usize, speed: Speed) -> usize {
+        let speed = speed.times(self.speed);
+        (execution * speed.unit)
+            .saturating_sub(self.carry)
+            .div_ceil(speed.cycles)
+    }
+
+    /// whether the job has used up its budget without completing
+    fn overran(&self) -> bool {
+        self.remaining > 0 && self.consumed() >= self.wcet
+    }
+
+    /// resources locked by the job at this point of its execution
+    fn held_resources(&self) -> impl Iterator<Item = usize> + '_ {
+        let consumed = self.consumed();
+        self.critical_sections
+            .iter()
+            .filter(move |cs| cs.offset < consumed && consumed < cs.offset + cs.length)
+            .map(|cs| cs.resource)
+    }
+
+    /// execution time left till the job enters or leaves a critical section
+    fn next_section_boundary(&self) -> Option<usize> {
+        let consumed = self.consumed();
+        self.critical_sections
+            .iter()
+            .flat_map(|cs| [cs.offset, cs.offset + cs.length])
+            .filter(|b| *b > consumed)
+            .min()
+            .map(|b| b - consumed)
+    }
+
+    pub(crate) fn is_finished(&self) -> bool {
+        matches!(
+            self.status,
+            JobStatus::DeadlineExceeded | JobStatus::Done | JobStatus::Dropped | JobStatus::Skipped
+        )
+    }
+
+    pub(crate) fn missed(&self) -> bool {
+        matches!(self.status, JobStatus::DeadlineExceeded)
+    }
+
+    /// moves the job to a processor running it at `speed`.
+    /// the execution towards its next tick is lost if the speed changes
+    pub(crate) fn migrate(&mut self, speed: Speed) {
+        if self.speed != speed {
+            self.speed = speed;
+            self.carry = 0;
         }
     }
-    fn run(&mut self, from: usize, to: usize) -> usize {
-        let untill = *[to, self.deadline, from + self.remaining]
+
+    /// the time a run started at `from` would stop at
+    pub(crate) fn run_end(&self, from: usize, to: usize) -> usize {
+        self.run_end_at(from, to, Speed::FULL)
+    }
+
+    fn run_end_at(&self, from: usize, to: usize, speed: Speed) -> usize {
+        let deadline = match self.deadline_miss.aborts() {
+            true => self.deadline,
+            false => usize::MAX,
+        };
+        *[to, deadline, from + self.time_left(speed)]
             .iter()
             .min()
-            .unwrap();
-        let duration = untill - from;
-        self.remaining -= duration;
+            .unwrap()
+    }
+
+    /// joins the runs of the log that follow each other without interruption
+    fn merge_log(&mut self) {
+        let log = std::mem::take(&mut self.log);
+        for (start, end) in log {
+            match self.log.last_mut() {
+                Some(last) if last.1 == start => last.1 = end,
+                _ => self.log.push((start, end)),
+            }
+        }
+    }
+
+    pub(crate) fn run(&mut self, from: usize, to: usize) -> usize {
+        self.run_at(from, to, Speed::FULL)
+    }
+
+    fn run_at(&mut self, from: usize, to: usize, processor: Speed) -> usize {
+        let untill = self.run_end_at(from, to, processor);
+        let speed = processor.times(self.speed);
+        let cycles = self.carry + (untill - from) * speed.cycles;
+        let executed = (cycles / speed.unit).min(self.remaining);
+        self.remaining -= executed;
+        self.executed += executed;
+        self.carry = match self.remaining {
+            0 => 0,
+            _ => cycles % speed.unit,
+        };
         self.log.push((from, untill));
 
-        if untill + self.remaining > self.deadline {
+        let aborted = match self.deadline_miss {
+            DeadlineMiss::AbortPredictively => untill + self.time_left(processor) > self.deadline,
+            DeadlineMiss::AbortAtDeadline => self.remaining > 0 && untill >= self.deadline,
+            DeadlineMiss::ContinueLate | DeadlineMiss::SkipNext => false,
+        };
+        if aborted {
             self.status = JobStatus::DeadlineExceeded;
         } else if self.remaining == 0 {
             self.status = JobStatus::Done;
@@ -57,13 +399,269 @@ impl Job {
     }
 }
 
+/// a one-off job that is not released by any task,
+/// e.g. an operator command or an event handler
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct AperiodicJob {
+    id: usize,
+    arrival_time: usize,
+    execution: usize,
+    #[serde(default)]
+    deadline: Option<usize>,
+    /// id of the server task serving the job, it runs in background when unset
+    #[serde(default)]
+    server: Option<usize>,
+}
+
+impl AperiodicJob {
+    pub fn new(id: usize, arrival_time: usize, execution: usize) -> Self {
+        Self {
+            id,
+            arrival_time,
+            execution,
+            deadline: None,
+            server: None,
+        }
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// sets an absolute soft deadline, only used for reporting
+    pub fn with_deadline(self, deadline: usize) -> Self {
+        Self {
+            deadline: Some(deadline),
+            ..self
+        }
+    }
+
+    /// has the job served by the server task with id `server`,
+    /// see `Task::server`
+    pub fn with_server(self, server: usize) -> Self {
+        Self {
+            server: Some(server),
+            ..self
+        }
+    }
+}
+
+impl From<AperiodicJob> for Job {
+    fn from(job: AperiodicJob) -> Self {
+        Self {
+            kind: JobKind::Aperiodic {
+                soft_deadline: job.deadline,
+                server: job.server,
+            },
+            ..Job::new(job.id, 0, job.arrival_time, job.execution, usize::MAX)
+                .with_period(usize::MAX)
+        }
+    }
+}
+
+/// when a running job can be preempted by a job of higher priority
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Preemption {
+    /// at any time
+    #[default]
+    Preemptive,
+    /// never, a job runs to completion once started
+    NonPreemptive,
+    /// only after every non-preemptive region of the task, see
+    /// `Task::with_non_preemptive_region`. tasks without one are fully preemptive
+    Limited,
+}
+
+/// what happens to a job that misses its deadline
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DeadlineMiss {
+    /// the job runs until its deadline and is aborted there
+    AbortAtDeadline,
+    /// the job is aborted as soon as it can't complete its execution
+    /// by its deadline anymore
+    #[default]
+    AbortPredictively,
+    /// the job runs late until it completes
+    ContinueLate,
+    /// the job runs late until it completes,
+    /// and the next job of its task is skipped
+    SkipNext,
+}
+
+impl DeadlineMiss {
+    fn aborts(self) -> bool {
+        matches!(
+            self,
+            DeadlineMiss::AbortAtDeadline | DeadlineMiss::AbortPredictively
+        )
+    }
+}
+
 pub struct JobList {
     jobs: Vec<Job>,
+    servers: Vec<Server>,
+    policy: Rc<dyn SchedulingPolicy>,
+    preemption: Preemption,
+    deadline_miss: DeadlineMiss,
+    overheads: Overheads,
+    dvfs: Option<Dvfs>,
+    /// the platform and the index of the processor the jobs run on
+    platform: Option<(Platform, usize)>,
+    /// period of the scheduler timer under tick-based scheduling
+    timer_tick: Option<usize>,
+    /// predecessors of every subtask of the dags the jobs belong to
+    precedence: HashMap<usize, Vec<usize>>,
+    /// virtual deadline scaling factor, enables EDF-VD when set
+    edf_vd: Option<f32>,
+    mode_switches: Vec<ModeSwitch>,
+    priority_inversions: Vec<PriorityInversion>,
+    context_switches: usize,
+    overhead_log: Vec<Overhead>,
+    frequencies: Vec<FrequencySegment>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct ModeSwitch {
+    time: usize,
+    mode: Criticality,
+}
+
+/// `blocked` was ready with a higher priority than `running` during `from..to`
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct PriorityInversion {
+    from: usize,
+    to: usize,
+    running: (usize, usize),
+    blocked: (usize, usize),
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TaskBlocking {
+    id: usize,
+    worst_case_blocking: usize,
+}
+
+#[derive(Serialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct TaskTardiness {
+    id: usize,
+    /// jobs that completed late or got aborted
+    deadline_misses: usize,
+    max_tardiness: usize,
+    total_tardiness: usize,
+}
+
+impl Default for JobList {
+    fn default() -> Self {
+        Self {
+            jobs: Vec::new(),
+            servers: Vec::new(),
+            policy: Rc::new(Edf),
+            preemption: Preemption::default(),
+            deadline_miss: DeadlineMiss::default(),
+            overheads: Overheads::default(),
+            dvfs: None,
+            platform: None,
+            timer_tick: None,
+            precedence: HashMap::new(),
+            edf_vd: None,
+            mode_switches: Vec::new(),
+            priority_inversions: Vec::new(),
+            context_switches: 0,
+            overhead_log: Vec::new(),
+            frequencies: Vec::new(),
+        }
+    }
 }
 
 impl JobList {
     pub fn new() -> Self {
-        Self { jobs: Vec::new() }
+        Self::default()
+    }
+
+    /// picks the next job to run with `policy`, EDF by default
+    pub fn with_policy(self, policy: impl SchedulingPolicy + 'static) -> Self {
+        Self {
+            policy: Rc::new(policy),
+            ..self
+        }
+    }
+
+    /// schedules the jobs with EDF-VD.
+    /// HI jobs get their relative deadline scaled by `factor` in LO mode.
+    /// a HI job overrunning its LO budget switches the processor to HI mode,
+    /// which drops the LO jobs until the next idle instant.
+    /// see `TaskList::edf_vd_factor`
+    pub fn with_edf_vd(self, factor: f32) -> Self {
+        Self {
+            edf_vd: Some(factor),
+            ..self
+        }
+    }
+
+    pub fn with_preemption(self, preemption: Preemption) -> Self {
+        Self { preemption, ..self }
+    }
+
+    /// handles the periodic jobs missing their deadlines with `deadline_miss`,
+    /// which aborts them predictively by default
+    pub fn with_deadline_miss(self, deadline_miss: DeadlineMiss) -> Self {
+        Self {
+            deadline_miss,
+            ..self
+        }
+    }
+
+    /// charges the processor with `overheads` on top of the execution of the jobs
+    pub fn with_overheads(self, overheads: Overheads) -> Self {
+        Self { overheads, ..self }
+    }
+
+    /// scales the frequency of the processor with the governor of `dvfs`
+    /// and reports the energy it spends.
+    /// execution times are taken at its highest frequency
+    pub fn with_dvfs(self, dvfs: Dvfs) -> Self {
+        Self {
+            dvfs: Some(dvfs),
+            ..self
+        }
+    }
+
+    /// runs the jobs on processor `index` of `platform`,
+    /// their execution times scale with its speed for them
+    pub fn with_platform(self, platform: Platform, index: usize) -> Self {
+        assert!(index < platform.num_proc(), "no such processor");
+        Self {
+            platform: Some((platform, index)),
+            ..self
+        }
+    }
+
+    /// takes scheduling decisions only at the interrupts of a timer of period `tick`,
+    /// as a tick-based kernel does: a job arriving between two interrupts waits for
+    /// the next one to be released. a job that completes still hands the processor
+    /// over right away
+    pub fn with_timer_tick(self, tick: usize) -> Self {
+        assert!(tick > 0, "empty timer tick");
+        Self {
+            timer_tick: Some(tick),
+            ..self
+        }
+    }
+
+    /// releases the jobs of the subtasks of `dags` only once the jobs of the same
+    /// instance of all their predecessors have completed.
+    /// predecessors that aren't in the list hold their successors back for good,
+    /// see `schedule_dags` for dags spanning several processors
+    pub fn with_dags(self, dags: &[DagTask]) -> Self {
+        Self {
+            precedence: precedence(dags),
+            ..self
+        }
     }
 
     pub fn push(&mut self, job: Job) {
@@ -76,115 +674,1455 @@ impl JobList {
 
     pub fn join(&mut self, other: Self) -> &mut Self {
         self.jobs.extend(other.jobs);
+        self.servers.extend(other.servers);
+        self.precedence.extend(other.precedence);
         self
     }
 
+    pub(crate) fn push_server(&mut self, server: Server) {
+        self.servers.push(server);
+    }
+
+    pub fn push_aperiodic(&mut self, job: AperiodicJob) {
+        self.jobs.push(job.into());
+    }
+
+    /// simulates the jobs up to the last arrival, the jobs still ready then are left out.
+    /// with precedence constraints, they are simulated up to the latest deadline instead.
+    /// see `schedule_until`
     pub fn schedule(&mut self) {
-        // arrival time decending
-        self.jobs.sort_by_key(|x| x.arrival_time);
-        self.jobs.reverse();
-
-        let mut finished_jobs = Vec::new();
-        let mut ready_jobs: Vec<Job> = Vec::new();
-        let mut now = 0;
-        while let Some(new_job) = self.jobs.pop() {
-            while now < new_job.arrival_time {
-                // sort by deadline decending
-                ready_jobs.sort_by_key(|x| x.deadline);
-                ready_jobs.reverse();
-
-                // ready_jobs can run in this slack time
-                if let Some(mut active_job) = ready_jobs.pop() {
-                    let duration = active_job.run(now, new_job.arrival_time);
-                    now += duration;
-                    match active_job.status {
-                        JobStatus::Ready | JobStatus::Running => ready_jobs.push(active_job),
-                        JobStatus::DeadlineExceeded | JobStatus::Done => {
-                            finished_jobs.push(active_job)
-                        }
+        if !self.precedence.is_empty() {
+            let horizon = self
+                .jobs
+                .iter()
+                .filter(|j| matches!(j.kind, JobKind::Periodic))
+                .map(|j| j.deadline)
+                .max();
+            return self.schedule_until(horizon.unwrap_or(0));
+        }
+        let last_release = self
+            .jobs
+            .iter()
+            .map(|j| next_tick(j.arrival_time, self.timer_tick))
+            .max();
+        let mut cpu = Cpu::new(self);
+        if let Some(last_release) = last_release {
+            cpu.advance(last_release);
+            cpu.handle_events();
+        }
+        cpu.finish(self);
+    }
+
+    /// simulates the jobs up to `horizon`, running the ready jobs past the
+    /// last arrival. the jobs left unfinished at the horizon are reported as such,
+    /// the ones arriving at or after it are kept as they are
+    pub fn schedule_until(&mut self, horizon: usize) {
+        if !self.precedence.is_empty() {
+            let precedence = std::mem::take(&mut self.precedence);
+            schedule_with_precedence(std::slice::from_mut(self), &precedence, horizon);
+            self.precedence = precedence;
+            return;
+        }
+        let mut cpu = Cpu::new(self);
+        let timer_tick = cpu.timer_tick;
+        let later = cpu.take_arrivals(|job| next_tick(job.arrival_time, timer_tick) >= horizon);
+        cpu.advance(horizon);
+        cpu.halt();
+        cpu.finished_jobs.extend(later);
+        cpu.finish(self);
+    }
+}
+
+/// simulates `joblists`, one per processor, in lockstep up to `horizon`.
+/// a job of a task listed in `precedence` is released once the job of the
+/// same iteration of every one of its predecessors is done, on any processor.
+/// a piece of a split job is released once the previous piece is done.
+/// returns the earliest completion time of every done `(task id, iteration)`,
+/// the one of its last piece for a split job
+pub(crate) fn schedule_with_precedence(
+    joblists: &mut [JobList],
+    precedence: &HashMap<usize, Vec<usize>>,
+    horizon: usize,
+) -> HashMap<(usize, usize), usize> {
+    let has_predecessors = |job: &Job| {
+        job.piece.is_some_and(|(index, _)| index > 0)
+            || precedence.get(&job.id).is_some_and(|p| !p.is_empty())
+    };
+    let mut cpus: Vec<Cpu> = joblists.iter_mut().map(Cpu::new).collect();
+    // jobs arriving at or after the horizon are kept as they are
+    let later: Vec<Vec<Job>> = cpus
+        .iter_mut()
+        .map(|cpu| {
+            let timer_tick = cpu.timer_tick;
+            cpu.take_arrivals(|job| next_tick(job.arrival_time, timer_tick) >= horizon)
+        })
+        .collect();
+    // jobs waiting for their predecessors, per processor
+    let mut gated: Vec<Vec<Job>> = cpus
+        .iter_mut()
+        .map(|cpu| {
+            // latest arrival first
+            let mut gated = cpu.take_arrivals(has_predecessors);
+            gated.reverse();
+            gated
+        })
+        .collect();
+    let mut completions = HashMap::new();
+    // completion time of every done (task id, iteration, piece index)
+    let mut piece_completions = HashMap::new();
+    // number of finished jobs already looked at, per processor
+    let mut seen = vec![0; cpus.len()];
+
+    let mut now = 0;
+    loop {
+        for (cpu, seen) in cpus.iter().zip(seen.iter_mut()) {
+            for job in &cpu.finished_jobs[*seen..] {
+                if let (JobStatus::Done, Some((_, end))) = (&job.status, job.log.last()) {
+                    if let Some((index, _)) = job.piece {
+                        piece_completions
+                            .entry((job.id, job.iteration, index))
+                            .or_insert(*end);
+                    }
+                    if job.piece.is_none_or(|(index, count)| index + 1 == count) {
+                        completions.entry((job.id, job.iteration)).or_insert(*end);
                     }
-                } else {
-                    break;
                 }
             }
-            now = new_job.arrival_time;
-            ready_jobs.push(new_job);
+            *seen = cpu.finished_jobs.len();
+        }
+
+        // the time the predecessors of `job` all completed at, if they did
+        let predecessors_done = |job: &Job| match job.piece {
+            Some((index, _)) if index > 0 => piece_completions
+                .get(&(job.id, job.iteration, index - 1))
+                .copied(),
+            _ => precedence[&job.id]
+                .iter()
+                .map(|p| completions.get(&(*p, job.iteration)).copied())
+                .collect::<Option<Vec<usize>>>()
+                .and_then(|ends| ends.into_iter().max()),
+        };
+        for (cpu, gated) in cpus.iter_mut().zip(gated.iter_mut()) {
+            let (released, waiting): (Vec<Job>, Vec<Job>) = std::mem::take(gated)
+                .into_iter()
+                .partition(|job| predecessors_done(job).is_some());
+            *gated = waiting;
+            for mut job in released {
+                job.precedence_release = predecessors_done(&job);
+                cpu.push_pending(job);
+            }
+        }
+
+        for cpu in cpus.iter_mut() {
+            cpu.handle_events();
+        }
+
+        if now >= horizon {
+            break;
         }
-        self.jobs = finished_jobs;
+        let next_arrival = cpus
+            .iter()
+            .filter_map(|cpu| cpu.events.next_time())
+            .min()
+            .unwrap_or(horizon)
+            .min(horizon);
+        // every processor runs at most one segment till the next event,
+        // so completions can release successors on other processors in time
+        let next = cpus
+            .iter()
+            .map(|cpu| cpu.segment_end(next_arrival))
+            .min()
+            .unwrap_or(next_arrival)
+            .max(now + 1);
+        for cpu in cpus.iter_mut() {
+            cpu.advance(next);
+        }
+        now = next;
+    }
+
+    for (((mut cpu, joblist), gated), later) in cpus
+        .into_iter()
+        .zip(joblists.iter_mut())
+        .zip(gated)
+        .zip(later)
+    {
+        // keep the jobs that are unfinished at the horizon
+        cpu.halt();
+        let (waiting, later_gated): (Vec<Job>, Vec<Job>) = gated
+            .into_iter()
+            .partition(|job| job.arrival_time < horizon);
+        cpu.finished_jobs
+            .extend(waiting.into_iter().map(Job::unfinished));
+        cpu.finished_jobs.extend(later_gated);
+        cpu.finished_jobs.extend(later);
+        // runs are cut at the events of every processor, not just this one
+        for job in cpu.finished_jobs.iter_mut() {
+            job.merge_log();
+        }
+        cpu.finish(joblist);
     }
+    completions
 }
 
 #[derive(Serialize)]
 #[serde(rename_all = "kebab-case")]
-pub struct JobReport {
+pub struct SplitReport {
     id: usize,
     iteration: usize,
+    release_time: usize,
+    deadline: usize,
+    /// completion time of the last piece
+    finish_time: Option<usize>,
+    /// done once the last piece is, missed as soon as any piece is
+    status: JobStatus,
+}
+
+/// the outcome of every job of the tasks split by semi-partitioning, from the
+/// jobs of their pieces on all the processors of `joblists` once simulated together.
+/// when split tasks are replicated, the first replica to finish counts
+pub fn split_reports(joblists: &[JobList]) -> Vec<SplitReport> {
+    let mut reports: BTreeMap<(usize, usize), SplitReport> = BTreeMap::new();
+    for job in joblists.iter().flat_map(|joblist| &joblist.jobs) {
+        let Some((index, count)) = job.piece else {
+            continue;
+        };
+        let report = reports
+            .entry((job.id, job.iteration))
+            .or_insert(SplitReport {
+                id: job.id,
+                iteration: job.iteration,
+                release_time: job.arrival_time,
+                deadline: job.deadline,
+                finish_time: None,
+                status: JobStatus::Unfinished,
+            });
+        if index == 0 {
+            report.release_time = job.arrival_time;
+        }
+        if index + 1 == count {
+            report.deadline = job.deadline;
+        }
+        match (&job.status, job.log.last()) {
+            (JobStatus::Done, Some((_, end))) if index + 1 == count => {
+                if report.finish_time.is_none_or(|finish| *end < finish) {
+                    report.finish_time = Some(*end);
+                }
+                report.status = JobStatus::Done;
+            }
+            (JobStatus::DeadlineExceeded | JobStatus::Skipped, _)
+                if report.finish_time.is_none() =>
+            {
+                report.status = JobStatus::DeadlineExceeded;
+            }
+            _ => {}
+        }
+    }
+    reports.into_values().collect()
+}
+
+/// the first tick of the timer at or after `time`, `time` itself without a timer
+fn next_tick(time: usize, timer_tick: Option<usize>) -> usize {
+    timer_tick.map_or(time, |tick| time.next_multiple_of(tick))
+}
+
+/// simulation state of a single processor
+struct Cpu {
+    now: usize,
+    events: EventQueue,
+    /// jobs are released at the ticks of the timer, when set
+    timer_tick: Option<usize>,
+    ready_jobs: ReadyQueue,
+    /// aperiodic jobs are served in background, in FIFO order
+    background_jobs: VecDeque<Job>,
+    servers: Vec<Server>,
+    finished_jobs: Vec<Job>,
+    mode: Criticality,
+    policy: Rc<dyn SchedulingPolicy>,
+    edf_vd: Option<f32>,
+    preemption: Preemption,
+    mode_switches: Vec<ModeSwitch>,
+    /// highest preemption level (smallest value) of the jobs using each resource
+    ceilings: HashMap<usize, usize>,
+    priority_inversions: Vec<PriorityInversion>,
+    /// (id, iteration) of the job that ran last
+    running: Option<(usize, usize)>,
+    /// the job dispatched while its context switch is paid,
+    /// it runs next unless an event comes first
+    switching: Option<(usize, usize)>,
+    context_switches: usize,
+    overheads: Overheads,
+    /// overheads yet to be spent, in order
+    owed: VecDeque<(OverheadKind, usize)>,
+    overhead_log: Vec<Overhead>,
+    dvfs: Option<Dvfs>,
+    speed: Speed,
+    /// utilization accounted for every task by cycle-conserving EDF
+    utilizations: BTreeMap<usize, f64>,
+    frequencies: Vec<FrequencySegment>,
+}
+
+impl Cpu {
+    /// takes the jobs of `joblist` to simulate them
+    fn new(joblist: &mut JobList) -> Self {
+        let mut pending = std::mem::take(&mut joblist.jobs);
+        pending.sort_by_key(|x| x.arrival_time);
+
+        for job in pending
+            .iter_mut()
+            .filter(|j| matches!(j.kind, JobKind::Periodic))
+        {
+            job.deadline_miss = joblist.deadline_miss;
+        }
+
+        if let Some((platform, index)) = &joblist.platform {
+            for job in pending.iter_mut() {
+                job.speed = match job.kind {
+                    JobKind::Periodic => platform.speed(job.id, *index),
+                    JobKind::Aperiodic { .. } => platform.processor_speed(*index),
+                };
+            }
+        }
+
+        if let Some(factor) = joblist.edf_vd {
+            for job in pending
+                .iter_mut()
+                .filter(|j| j.criticality == Criticality::Hi)
+            {
+                job.virtual_deadline =
+                    job.arrival_time + (factor * job.relative_deadline as f32) as usize;
+            }
+        }
+
+        let mut ceilings = HashMap::new();
+        for job in &pending {
+            for cs in &job.critical_sections {
+                let ceiling = ceilings.entry(cs.resource).or_insert(usize::MAX);
+                *ceiling = job.preemption_level.min(*ceiling);
+            }
+        }
+
+        let mut cpu = Self {
+            now: 0,
+            events: EventQueue::default(),
+            timer_tick: joblist.timer_tick,
+            ready_jobs: ReadyQueue::new(joblist.policy.clone()),
+            background_jobs: VecDeque::new(),
+            servers: std::mem::take(&mut joblist.servers),
+            finished_jobs: Vec::new(),
+            mode: Criticality::Lo,
+            policy: joblist.policy.clone(),
+            edf_vd: joblist.edf_vd,
+            preemption: joblist.preemption,
+            mode_switches: Vec::new(),
+            ceilings,
+            priority_inversions: Vec::new(),
+            running: None,
+            switching: None,
+            context_switches: 0,
+            overheads: joblist.overheads,
+            owed: VecDeque::new(),
+            overhead_log: Vec::new(),
+            dvfs: None,
+            speed: Speed::FULL,
+            utilizations: BTreeMap::new(),
+            frequencies: Vec::new(),
+        }
+        .with_dvfs(joblist.dvfs.clone(), &pending);
+        for job in pending {
+            cpu.push_pending(job);
+        }
+        for i in 0..cpu.servers.len() {
+            cpu.schedule_replenishment(i);
+        }
+        cpu
+    }
+
+    /// starts at the frequency the governor of `dvfs` picks for `jobs`
+    fn with_dvfs(mut self, dvfs: Option<Dvfs>, jobs: &[Job]) -> Self {
+        let Some(dvfs) = dvfs else {
+            return self;
+        };
+        let mut utilizations = BTreeMap::new();
+        for job in jobs.iter().filter(|j| matches!(j.kind, JobKind::Periodic)) {
+            utilizations.insert(job.id, job.utilization(job.wcet));
+        }
+        for server in &self.servers {
+            utilizations.insert(server.id, server.utilization());
+        }
+        let frequency = match dvfs.governor() {
+            Governor::Maximum => dvfs.max_frequency(),
+            Governor::StaticSlowdown | Governor::CycleConserving => {
+                dvfs.lowest_frequency(utilizations.values().sum())
+            }
+        };
+        self.speed = Speed {
+            cycles: frequency,
+            unit: dvfs.max_frequency(),
+        };
+        self.utilizations = utilizations;
+        self.dvfs = Some(dvfs);
+        self
+    }
+
+    /// ends the simulation, the jobs that are waiting to run or complete are unfinished
+    fn halt(&mut self) {
+        let ready_jobs = self.ready_jobs.drain();
+        let background_jobs = std::mem::take(&mut self.background_jobs);
+        let requests = self.servers.iter_mut().flat_map(|s| s.queue.drain(..));
+        let unfinished: Vec<Job> = ready_jobs
+            .into_iter()
+            .chain(background_jobs)
+            .chain(requests)
+            .collect();
+        self.finished_jobs
+            .extend(unfinished.into_iter().map(Job::unfinished));
+    }
+
+    /// hands the simulated jobs back to `joblist`
+    fn finish(mut self, joblist: &mut JobList) {
+        // keep unfinished aperiodic jobs so their response time shows up as missing
+        self.finished_jobs.extend(self.background_jobs);
+        for server in self.servers.iter_mut() {
+            self.finished_jobs.extend(server.queue.drain(..));
+        }
+        joblist.jobs = self.finished_jobs;
+        joblist.servers = self.servers;
+        joblist.mode_switches = self.mode_switches;
+        joblist.priority_inversions = self.priority_inversions;
+        joblist.context_switches = self.context_switches;
+        joblist.overhead_log = self.overhead_log;
+        joblist.frequencies = self.frequencies;
+    }
+
+    /// adds a job that arrives in the future
+    fn push_pending(&mut self, job: Job) {
+        let release = next_tick(job.release_time(), self.timer_tick);
+        self.events.push(release, Event::Arrival(job));
+    }
+
+    /// removes the jobs yet to arrive that match `predicate`, by arrival time
+    fn take_arrivals(&mut self, predicate: impl Fn(&Job) -> bool) -> Vec<Job> {
+        self.events
+            .take(|event| matches!(event, Event::Arrival(job) if predicate(job)))
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::Arrival(job) => Some(job),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn priority(&self, job: &Job) -> (usize, usize) {
+        (
+            self.policy.priority(job, self.now),
+            self.policy.tie_break(job),
+        )
+    }
+
+    /// (id, iteration) of the ready job to run instead of `candidate`,
+    /// which is the job that ran last if the policy lets it keep the processor
+    fn keep_running(&self, candidate: &Job, ceiling: usize) -> (usize, usize) {
+        self.running
+            .filter(|key| {
+                self.ready_jobs.get(*key).is_some_and(|j| {
+                    Self::eligible(j, ceiling) && self.policy.keep_running(j, candidate, self.now)
+                })
+            })
+            .unwrap_or((candidate.id, candidate.iteration))
+    }
+
+    /// the ready job whose context switch was just paid
+    fn switched(&self) -> Option<(usize, usize)> {
+        self.switching
+            .filter(|key| self.owed.is_empty() && self.ready_jobs.get(*key).is_some())
+    }
+
+    /// the job that ran last, if it's ready and can't be preempted
+    fn non_preemptible_job(&self) -> Option<(usize, usize)> {
+        self.running.filter(|key| {
+            self.ready_jobs
+                .get(*key)
+                .is_some_and(|j| self.non_preemptible(j))
+        })
+    }
+
+    /// the server with the highest priority that has a request to serve,
+    /// if it has a higher priority than the ready job `key`
+    fn server_candidate(&self, key: Option<(usize, usize)>, ceiling: usize) -> Option<usize> {
+        let job = key.and_then(|k| self.ready_jobs.get(k));
+        if job.is_some_and(|j| self.non_preemptible(j)) {
+            return None;
+        }
+        self.servers
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.is_active())
+            .map(|(i, s)| (i, self.priority(&s.proxy())))
+            .filter(|(i, _)| Self::eligible(&self.servers[*i].proxy(), ceiling))
+            .min_by_key(|(_, key)| *key)
+            .filter(|(_, key)| job.is_none_or(|j| *key < self.priority(j)))
+            .map(|(i, _)| i)
+    }
+
+    /// the point the request at the head of the server at `index`
+    /// has to be stopped at, at the latest
+    fn server_limit(&self, index: usize, until: usize) -> usize {
+        let server = &self.servers[index];
+        self.limit(&server.proxy(), until)
+            .min(self.now + server.capacity())
+    }
+
+    /// serves the request at the head of the server at `index`
+    fn serve(&mut self, index: usize, until: usize) {
+        let until = self.server_limit(index, until);
+        let mut request = self.servers[index].queue.pop_front().unwrap();
+        let duration = request.run_at(self.now, until, self.speed);
+        self.now += duration;
+        match request.status {
+            JobStatus::Done => self.events.push(self.now, Event::Completion(request)),
+            _ => self.servers[index].queue.push_front(request),
+        }
+        let server = &mut self.servers[index];
+        let id = server.id;
+        if let Some(time) = server.consume(duration, self.now) {
+            self.events.push(time, Event::Replenishment { server: id });
+        }
+        if server.capacity() == 0 {
+            self.events
+                .push(self.now, Event::ServerExhaustion { server: id });
+        }
+    }
+
+    /// posts the start of the next period of the server at `index`, if it has periods
+    fn schedule_replenishment(&mut self, index: usize) {
+        let server = &self.servers[index];
+        if let Some(time) = server.next_period() {
+            self.events
+                .push(time, Event::Replenishment { server: server.id });
+        }
+    }
+
+    /// counts a context switch if `job` isn't the one that ran last.
+    /// returns true if its overhead took the processor till `until` or less,
+    /// in which case `job` is yet to run
+    fn dispatch(&mut self, job: (usize, usize), until: usize) -> bool {
+        if self.running == Some(job) {
+            return false;
+        }
+        if let Some(previous) = self.running.and_then(|key| self.waiting_job(key)) {
+            if !previous.log.is_empty() {
+                previous.preemptions += 1;
+            }
+        }
+        self.running = Some(job);
+        self.context_switches += 1;
+        self.owe(OverheadKind::ContextSwitch);
+        if !self.owed.is_empty() {
+            self.switching = Some(job);
+        }
+        self.charge(until)
+    }
+
+    /// the ready, background or server job with (id, iteration) `key`
+    fn waiting_job(&mut self, key: (usize, usize)) -> Option<&mut Job> {
+        match self.ready_jobs.get_mut(key) {
+            Some(job) => Some(job),
+            None => self
+                .background_jobs
+                .iter_mut()
+                .chain(self.servers.iter_mut().flat_map(|s| s.queue.iter_mut()))
+                .find(|j| (j.id, j.iteration) == key),
+        }
+    }
+
+    fn owe(&mut self, kind: OverheadKind) {
+        let cost = self.overheads.cost(kind);
+        if cost > 0 {
+            self.owed.push_back((kind, cost));
+        }
+    }
+
+    /// spends the time till `until` at the latest on the overheads owed.
+    /// returns false if there are none
+    fn charge(&mut self, until: usize) -> bool {
+        let Some((kind, cost)) = self.owed.front_mut() else {
+            return false;
+        };
+        let end = until.min(self.now + *cost);
+        *cost -= end - self.now;
+        self.overhead_log.push(Overhead {
+            kind: *kind,
+            from: self.now,
+            to: end,
+        });
+        if *cost == 0 {
+            self.owed.pop_front();
+        }
+        self.now = end;
+        true
+    }
+
+    /// accounts `job` with a utilization of `execution` under cycle-conserving EDF,
+    /// and picks the frequency again
+    fn reclaim(&mut self, job: &Job, execution: usize) {
+        let Some(dvfs) = self
+            .dvfs
+            .as_ref()
+            .filter(|d| d.governor() == Governor::CycleConserving)
+        else {
+            return;
+        };
+        self.utilizations.insert(job.id, job.utilization(execution));
+        self.speed.cycles = dvfs.lowest_frequency(self.utilizations.values().sum());
+    }
+
+    /// the time a context switch to `job` started now would end at, if there is one
+    fn switch_end(&self, job: &Job, until: usize) -> Option<usize> {
+        let cost = self.overheads.cost(OverheadKind::ContextSwitch);
+        (self.running != Some((job.id, job.iteration)) && cost > 0)
+            .then(|| until.min(self.now + cost))
+    }
+
+    /// the point the running job has to be stopped at, at the latest
+    fn limit(&self, job: &Job, until: usize) -> usize {
+        let mut until = until;
+        if let Some(quantum) = self.policy.quantum() {
+            until = until.min(self.now + quantum);
+        }
+        // the points below are in execution, which takes longer or shorter on the processor
+        let at = |execution: usize| self.now + job.time_for(execution, self.speed);
+        if self.edf_vd.is_some() && self.mode == Criticality::Lo {
+            // stop at the LO budget to detect overruns
+            until = until.min(at(job.wcet.saturating_sub(job.consumed())));
+        }
+        if let Some(boundary) = job.next_section_boundary() {
+            // the system ceiling changes at section boundaries
+            until = until.min(at(boundary));
+        }
+        if let (Preemption::Limited, Some(length)) = (self.preemption, job.non_preemptive_region) {
+            // preemption point
+            until = until.min(at(length - job.consumed() % length));
+        }
+        until
+    }
+
+    /// whether `job` has started a non-preemptive region it hasn't finished yet
+    fn non_preemptible(&self, job: &Job) -> bool {
+        match self.preemption {
+            Preemption::Preemptive => false,
+            Preemption::NonPreemptive => !job.log.is_empty(),
+            Preemption::Limited => job
+                .non_preemptive_region
+                .is_some_and(|length| !job.consumed().is_multiple_of(length)),
+        }
+    }
+
+    /// Stack Resource Policy ceiling: the highest ceiling of the locked resources
+    fn system_ceiling(&self) -> usize {
+        if self.ceilings.is_empty() {
+            return usize::MAX;
+        }
+        self.ready_jobs
+            .iter()
+            .flat_map(Job::held_resources)
+            .map(|r| self.ceilings[&r])
+            .min()
+            .unwrap_or(usize::MAX)
+    }
+
+    /// a job can only start once its preemption level is above the system ceiling.
+    /// started jobs never block under SRP
+    fn eligible(job: &Job, ceiling: usize) -> bool {
+        !job.log.is_empty() || job.preemption_level < ceiling
+    }
+
+    /// logs the ready jobs with a higher priority than `active_job`
+    /// as blocked during `from..to`
+    fn record_blocking(&mut self, active_job: &Job, from: usize, to: usize) {
+        if from == to {
+            return;
+        }
+        // priorities as of the decision at `from`
+        let priority = self.policy.priority(active_job, from);
+        for job in self.ready_jobs.above_mut(priority) {
+            match job.blocked.last_mut() {
+                Some(last) if last.1 == from => last.1 = to,
+                _ => job.blocked.push((from, to)),
+            }
+            let running = (active_job.id, active_job.iteration);
+            let blocked = (job.id, job.iteration);
+            match self
+                .priority_inversions
+                .iter_mut()
+                .rev()
+                .find(|i| i.running == running && i.blocked == blocked && i.to == from)
+            {
+                Some(inversion) => inversion.to = to,
+                None => self.priority_inversions.push(PriorityInversion {
+                    from,
+                    to,
+                    running,
+                    blocked,
+                }),
+            }
+        }
+    }
+
+    /// time at which the next execution segment started before `until` ends,
+    /// without running it
+    fn segment_end(&self, until: usize) -> usize {
+        if let Some((_, cost)) = self.owed.front() {
+            return until.min(self.now + cost);
+        }
+        let ceiling = self.system_ceiling();
+        let key = self.switched().or_else(|| {
+            self.non_preemptible_job().or_else(|| {
+                self.ready_jobs
+                    .iter()
+                    .filter(|j| !j.expires_at(self.now) && Self::eligible(j, ceiling))
+                    .min_by_key(|j| self.priority(j))
+                    .map(|j| self.keep_running(j, ceiling))
+            })
+        });
+        if let Some(server) = self.server_candidate(key, ceiling) {
+            let request = self.servers[server].queue.front().unwrap();
+            return self.switch_end(request, until).unwrap_or_else(|| {
+                request.run_end_at(self.now, self.server_limit(server, until), self.speed)
+            });
+        }
+        let next_job = key
+            .and_then(|k| self.ready_jobs.get(k))
+            .map(|j| (j, self.limit(j, until)))
+            .or_else(|| self.background_jobs.front().map(|j| (j, until)));
+        match next_job {
+            Some((job, until)) => self
+                .switch_end(job, until)
+                .unwrap_or_else(|| job.run_end_at(self.now, until, self.speed)),
+            None => until,
+        }
+    }
+
+    /// runs one execution segment ending no later than `until`.
+    /// returns false if the processor is idle
+    fn step(&mut self, until: usize) -> bool {
+        if self.mode == Criticality::Hi && self.ready_jobs.is_empty() {
+            // idle instant, go back to LO mode
+            self.handle(Event::ModeChange(Criticality::Lo));
+        }
+
+        // the deadlines that passed while a job ran
+        while let Some(event) = self.events.pop_deferred(self.now) {
+            self.handle(event);
+        }
+
+        if self.charge(until) {
+            return true;
+        }
+
+        let ceiling = self.system_ceiling();
+        // the end of a context switch isn't a decision of its own,
+        // which would hand the processor over to a job of equal priority
+        let switched = self.switched();
+        self.switching = None;
+        if switched.is_none() {
+            self.ready_jobs.sort(self.now);
+        }
+
+        // ready_jobs can run in this slack time
+        let key = switched.or_else(|| {
+            self.non_preemptible_job().or_else(|| {
+                self.ready_jobs
+                    .iter()
+                    .rev()
+                    .find(|j| Self::eligible(j, ceiling))
+                    .map(|j| self.keep_running(j, ceiling))
+            })
+        });
+        if let Some(server) = self.server_candidate(key, ceiling) {
+            let request = self.servers[server].queue.front().unwrap();
+            if !self.dispatch((request.id, request.iteration), until) {
+                self.serve(server, until);
+            }
+            return true;
+        }
+        if let Some(key) = key {
+            if self.dispatch(key, until) {
+                return true;
+            }
+            let mut active_job = self.ready_jobs.remove(key).unwrap();
+            let until = self.limit(&active_job, until);
+            let start = self.now;
+            let duration = active_job.run_at(self.now, until, self.speed);
+            self.now += duration;
+            self.record_blocking(&active_job, start, self.now);
+            if self.edf_vd.is_some() && self.mode == Criticality::Lo && active_job.overran() {
+                self.events.push(self.now, Event::BudgetExhaustion);
+            }
+            match active_job.status {
+                JobStatus::Ready | JobStatus::Running => self.ready_jobs.push(active_job),
+                JobStatus::DeadlineExceeded | JobStatus::Done => {
+                    self.events.push(self.now, Event::Completion(active_job))
+                }
+                JobStatus::Dropped | JobStatus::Skipped | JobStatus::Unfinished => {
+                    self.finished_jobs.push(active_job)
+                }
+            }
+            true
+        } else if let Some(job) = self.background_jobs.front() {
+            if self.dispatch((job.id, job.iteration), until) {
+                return true;
+            }
+            let mut active_job = self.background_jobs.pop_front().unwrap();
+            let duration = active_job.run_at(self.now, until, self.speed);
+            self.now += duration;
+            match active_job.status {
+                JobStatus::Done => self.events.push(self.now, Event::Completion(active_job)),
+                _ => self.background_jobs.push_front(active_job),
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    /// executes the ready jobs and idles up to `to`, handling the events on the way.
+    /// the arrivals at `to` are left to `handle_events`
+    fn advance(&mut self, to: usize) {
+        loop {
+            let event = match self.now < to {
+                true => self.events.pop(self.now),
+                false => self.events.pop_before_arrivals(self.now),
+            };
+            if let Some(event) = event {
+                self.handle(event);
+                continue;
+            }
+            if self.now >= to {
+                break;
+            }
+            // decisions are revisited at the next event
+            let until = self.events.next_time().map_or(to, |t| t.min(to));
+            let (start, frequency) = (self.now, self.speed.cycles);
+            if self.step(until) {
+                if self.dvfs.is_some() {
+                    self.log_frequency(start, frequency);
+                }
+            } else {
+                self.now = until;
+            }
+        }
+    }
+
+    /// handles the events due by now
+    fn handle_events(&mut self) {
+        while let Some(event) = self.events.pop(self.now) {
+            self.handle(event);
+        }
+    }
+
+    fn handle(&mut self, event: Event) {
+        self.switching = None;
+        match event {
+            Event::Arrival(mut job) => {
+                if self.timer_tick.is_some() {
+                    job.release_latency = Some(self.now - job.release_time());
+                }
+                self.admit(job)
+            }
+            Event::Completion(job) => {
+                self.owe(OverheadKind::Completion);
+                if matches!(
+                    (&job.kind, &job.status),
+                    (JobKind::Periodic, JobStatus::Done)
+                ) {
+                    self.reclaim(&job, job.consumed());
+                }
+                self.finished_jobs.push(job)
+            }
+            Event::Deadline { id, iteration } => {
+                if let Some(job) = self.ready_jobs.remove((id, iteration)) {
+                    self.finished_jobs.push(job.expired());
+                    // the expired job takes a decision of its own, as if it got
+                    // picked and aborted right away, which flips the ties
+                    self.ready_jobs.sort(self.now);
+                }
+            }
+            Event::BudgetExhaustion => {
+                if self.mode == Criticality::Lo {
+                    self.handle(Event::ModeChange(Criticality::Hi));
+                }
+            }
+            Event::ServerExhaustion { server } => {
+                let now = self.now;
+                if let Some(server) = self.servers.iter_mut().find(|s| s.id == server) {
+                    server.exhaust(now);
+                }
+            }
+            Event::Replenishment { server } => {
+                let now = self.now;
+                if let Some(index) = self.servers.iter().position(|s| s.id == server) {
+                    self.servers[index].update(now);
+                    self.schedule_replenishment(index);
+                }
+            }
+            Event::ModeChange(mode) => self.switch_mode(mode),
+        }
+    }
+
+    /// LO jobs are dropped in HI mode, where HI jobs are scheduled by their real deadlines
+    fn switch_mode(&mut self, mode: Criticality) {
+        self.mode = mode;
+        self.mode_switches.push(ModeSwitch {
+            time: self.now,
+            mode,
+        });
+        if mode == Criticality::Hi {
+            let (lo_jobs, hi_jobs): (Vec<Job>, Vec<Job>) = self
+                .ready_jobs
+                .drain()
+                .into_iter()
+                .partition(|j| j.criticality == Criticality::Lo);
+            for mut job in hi_jobs {
+                job.virtual_deadline = job.deadline;
+                self.ready_jobs.push(job);
+            }
+            self.finished_jobs
+                .extend(lo_jobs.into_iter().map(Job::dropped));
+        }
+    }
+
+    /// makes `job` ready, to be aborted at its deadline if its policy says so
+    fn make_ready(&mut self, job: Job) {
+        if job.deadline_miss.aborts() {
+            let (id, iteration) = (job.id, job.iteration);
+            self.events
+                .push(job.deadline, Event::Deadline { id, iteration });
+        }
+        self.ready_jobs.push(job)
+    }
+
+    /// whether the job of the task of `job` released before it
+    /// has missed its deadline by now
+    fn previous_missed(&self, job: &Job) -> bool {
+        let previous = |j: &&Job| j.id == job.id && j.iteration + 1 == job.iteration;
+        self.ready_jobs
+            .iter()
+            .filter(previous)
+            .any(|j| j.deadline <= self.now)
+            || self
+                .finished_jobs
+                .iter()
+                .filter(previous)
+                .any(|j| j.tardiness().is_some_and(|t| t > 0))
+    }
+
+    /// logs the processor as busy at `frequency` since `start`
+    fn log_frequency(&mut self, start: usize, frequency: usize) {
+        if start == self.now {
+            return;
+        }
+        match self.frequencies.last_mut() {
+            Some(last) if last.to == start && last.frequency == frequency => last.to = self.now,
+            _ => self.frequencies.push(FrequencySegment {
+                from: start,
+                to: self.now,
+                frequency,
+            }),
+        }
+    }
+
+    /// makes `job` ready at the current time
+    fn admit(&mut self, new_job: Job) {
+        self.owe(OverheadKind::Release);
+        if matches!(new_job.kind, JobKind::Periodic) {
+            self.reclaim(&new_job, new_job.wcet);
+        }
+        match new_job.kind {
+            JobKind::Periodic
+                if new_job.deadline_miss == DeadlineMiss::SkipNext
+                    && self.previous_missed(&new_job) =>
+            {
+                self.finished_jobs.push(new_job.skipped())
+            }
+            JobKind::Periodic
+                if self.mode == Criticality::Hi && new_job.criticality == Criticality::Lo =>
+            {
+                self.finished_jobs.push(new_job.dropped())
+            }
+            JobKind::Periodic if self.mode == Criticality::Hi => {
+                let mut new_job = new_job;
+                new_job.virtual_deadline = new_job.deadline;
+                self.make_ready(new_job)
+            }
+            JobKind::Periodic => self.make_ready(new_job),
+            JobKind::Aperiodic {
+                server: Some(id), ..
+            } if self.servers.iter().any(|s| s.id == id) => {
+                let now = self.now;
+                let server = self.servers.iter_mut().find(|s| s.id == id).unwrap();
+                server.arrive(new_job, now)
+            }
+            JobKind::Aperiodic { .. } => self.background_jobs.push_back(new_job),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct JobReport {
+    pub(crate) id: usize,
+    #[serde(flatten)]
+    metadata: Metadata,
+    iteration: usize,
     arrival_time: usize,
     deadline: usize,
+    relative_deadline: usize,
+    criticality: Criticality,
     start_time: usize,
     finish_time: usize,
     remaining: usize,
     wcet: usize,
+    consumed: usize,
+    log: Vec<(usize, usize)>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    blocked: Vec<(usize, usize)>,
+    /// time from the deadline to the completion of the job, 0 if it's on time.
+    /// unset if the job didn't complete
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tardiness: Option<usize>,
+    preemptions: usize,
+    /// time the job waited for the timer to be released, under tick-based scheduling
+    #[serde(skip_serializing_if = "Option::is_none")]
+    release_latency: Option<usize>,
+    /// time the last predecessor of the job completed, under precedence constraints
+    #[serde(skip_serializing_if = "Option::is_none")]
+    precedence_release: Option<usize>,
+    status: JobStatus,
+}
+
+impl From<&Job> for JobReport {
+    fn from(job: &Job) -> Self {
+        let consumed = job.consumed();
+        let start_time = job.log.first().unwrap_or(&(0, 0)).0;
+        let finish_time = job.log.last().unwrap_or(&(0, 0)).1;
+        Self {
+            id: job.id,
+            metadata: (*job.metadata).clone(),
+            iteration: job.iteration,
+            arrival_time: job.arrival_time,
+            deadline: job.deadline,
+            relative_deadline: job.relative_deadline,
+            criticality: job.criticality,
+            start_time,
+            finish_time,
+            remaining: job.remaining,
+            wcet: job.wcet,
+            consumed,
+            log: job.log.clone(),
+            blocked: job.blocked.clone(),
+            tardiness: job.tardiness(),
+            preemptions: job.preemptions,
+            release_latency: job.release_latency,
+            precedence_release: job.precedence_release,
+            status: job.status.clone(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AperiodicJobReport {
+    id: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    server: Option<usize>,
+    arrival_time: usize,
+    deadline: Option<usize>,
+    execution: usize,
+    start_time: Option<usize>,
+    finish_time: Option<usize>,
+    response_time: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    release_latency: Option<usize>,
     log: Vec<(usize, usize)>,
     status: JobStatus,
 }
 
-impl From<&Job> for JobReport {
-    fn from(job: &Job) -> Self {
-        let wcet = job.log.iter().map(|(s, e)| e - s).sum::<usize>() + job.remaining;
-        let start_time = job.log.first().unwrap_or(&(0, 0)).0;
-        let finish_time = job.log.last().unwrap_or(&(0, 0)).1;
+impl From<&Job> for AperiodicJobReport {
+    fn from(job: &Job) -> Self {
+        let server = match job.kind {
+            JobKind::Aperiodic { server, .. } => server,
+            JobKind::Periodic => None,
+        };
+        let deadline = match job.kind {
+            JobKind::Aperiodic { soft_deadline, .. } => soft_deadline,
+            JobKind::Periodic => Some(job.deadline),
+        };
+        let finish_time = match job.status {
+            JobStatus::Done => job.log.last().map(|(_, e)| *e),
+            _ => None,
+        };
+        Self {
+            id: job.id,
+            server,
+            arrival_time: job.arrival_time,
+            deadline,
+            execution: job.consumed() + job.remaining,
+            start_time: job.log.first().map(|(s, _)| *s),
+            finish_time,
+            response_time: finish_time.map(|f| f - job.arrival_time),
+            release_latency: job.release_latency,
+            log: job.log.clone(),
+            status: job.status.clone(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ServerReport {
+    id: usize,
+    #[serde(flatten)]
+    metadata: Metadata,
+    kind: ServerKind,
+    /// (time, budget left) at every change of the budget
+    budget: Vec<(usize, usize)>,
+}
+
+impl From<&Server> for ServerReport {
+    fn from(server: &Server) -> Self {
+        Self {
+            id: server.id,
+            metadata: (*server.metadata).clone(),
+            kind: server.kind(),
+            budget: server.budget_log.clone(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Report {
+    jobs: Vec<JobReport>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    aperiodic_jobs: Vec<AperiodicJobReport>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    servers: Vec<ServerReport>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    mode_switches: Vec<ModeSwitch>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    priority_inversions: Vec<PriorityInversion>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    worst_case_blocking: Vec<TaskBlocking>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tardiness: Vec<TaskTardiness>,
+    /// number of times the processor started running a job other than the last one it ran
+    context_switches: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    overheads: Vec<Overhead>,
+    /// time the processor spent on overheads
+    total_overhead: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    frequencies: Vec<FrequencySegment>,
+    /// energy spent by the processor, in power units times the unit of time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    energy: Option<f64>,
+    /// unit of the reported times, they are in ticks when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolution: Option<ReportResolution>,
+    timeline: Vec<Slot>,
+}
+
+/// what a processor does in a slot of the timeline of its report
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Slot {
+    /// (id, iteration) of the running job, (0, 0) when idle
+    Job(usize, usize),
+    /// spending overheads
+    Overhead,
+}
+
+/// `[id, iteration]` for a job, as in `JobList::timeline`, and `"overhead"` otherwise
+impl Serialize for Slot {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Slot::Job(id, iteration) => (id, iteration).serialize(serializer),
+            Slot::Overhead => serializer.serialize_str("overhead"),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct ReportResolution {
+    unit: TimeUnit,
+    /// length of a timeline slot in `unit`
+    tick: usize,
+}
+
+fn scale_log(log: &mut [(usize, usize)], factor: usize) {
+    for (start, end) in log.iter_mut() {
+        *start *= factor;
+        *end *= factor;
+    }
+}
+
+impl JobReport {
+    fn scale(&mut self, factor: usize) {
+        self.arrival_time *= factor;
+        self.deadline *= factor;
+        self.relative_deadline *= factor;
+        self.start_time *= factor;
+        self.finish_time *= factor;
+        self.remaining *= factor;
+        self.wcet *= factor;
+        self.consumed *= factor;
+        scale_log(&mut self.log, factor);
+        scale_log(&mut self.blocked, factor);
+        for time in [
+            &mut self.tardiness,
+            &mut self.release_latency,
+            &mut self.precedence_release,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *time *= factor;
+        }
+    }
+}
+
+impl AperiodicJobReport {
+    fn scale(&mut self, factor: usize) {
+        self.arrival_time *= factor;
+        self.execution *= factor;
+        for time in [
+            &mut self.deadline,
+            &mut self.start_time,
+            &mut self.finish_time,
+            &mut self.response_time,
+            &mut self.release_latency,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *time *= factor;
+        }
+        scale_log(&mut self.log, factor);
+    }
+}
+
+impl Report {
+    /// states every reported time in `unit`, given ticks of `resolution`.
+    /// the timeline keeps one slot per tick.
+    /// fails if a tick is not a whole number of `unit`s
+    pub fn in_unit(mut self, resolution: &Resolution, unit: TimeUnit) -> Result<Self, TimeError> {
+        let factor = resolution.per_tick(unit)?;
+        for job in self.jobs.iter_mut() {
+            job.scale(factor);
+        }
+        for job in self.aperiodic_jobs.iter_mut() {
+            job.scale(factor);
+        }
+        for server in self.servers.iter_mut() {
+            scale_log(&mut server.budget, factor);
+        }
+        for switch in self.mode_switches.iter_mut() {
+            switch.time *= factor;
+        }
+        for inversion in self.priority_inversions.iter_mut() {
+            inversion.from *= factor;
+            inversion.to *= factor;
+        }
+        for blocking in self.worst_case_blocking.iter_mut() {
+            blocking.worst_case_blocking *= factor;
+        }
+        for tardiness in self.tardiness.iter_mut() {
+            tardiness.max_tardiness *= factor;
+            tardiness.total_tardiness *= factor;
+        }
+        for overhead in self.overheads.iter_mut() {
+            overhead.from *= factor;
+            overhead.to *= factor;
+        }
+        self.total_overhead *= factor;
+        for segment in self.frequencies.iter_mut() {
+            segment.from *= factor;
+            segment.to *= factor;
+        }
+        if let Some(energy) = self.energy.as_mut() {
+            *energy *= factor as f64;
+        }
+        self.resolution = Some(ReportResolution { unit, tick: factor });
+        Ok(self)
+    }
+
+    /// energy spent by the processor, if its frequency is scaled
+    pub fn energy(&self) -> Option<f64> {
+        self.energy
+    }
+
+    /// keeps the reports of the tasks that match `selector`, and of their jobs.
+    /// aperiodic jobs go with their server, and match only an empty selector
+    /// when served in background. the slots of the jobs left out read as idle
+    pub fn filter(self, selector: &Selector) -> Self {
+        let servers: Vec<ServerReport> = self
+            .servers
+            .into_iter()
+            .filter(|s| selector.matches(&s.metadata))
+            .collect();
+        let background = selector.matches(&Metadata::default());
+        let aperiodic_jobs: Vec<AperiodicJobReport> = self
+            .aperiodic_jobs
+            .into_iter()
+            .filter(|j| match j.server {
+                Some(id) => servers.iter().any(|s| s.id == id),
+                None => background,
+            })
+            .collect();
+        let jobs: Vec<JobReport> = self
+            .jobs
+            .into_iter()
+            .filter(|j| selector.matches(&j.metadata))
+            .collect();
+        let tasks: HashSet<usize> = jobs.iter().map(|j| j.id).collect();
+        let selected: HashSet<(usize, usize)> = jobs
+            .iter()
+            .map(|j| (j.id, j.iteration))
+            .chain(aperiodic_jobs.iter().map(|j| (j.id, 0)))
+            .collect();
         Self {
-            id: job.id,
-            iteration: job.iteration,
-            arrival_time: job.arrival_time,
-            deadline: job.deadline,
-            start_time,
-            finish_time,
-            remaining: job.remaining,
-            wcet,
-            log: job.log.clone(),
-            status: job.status.clone(),
+            jobs,
+            aperiodic_jobs,
+            servers,
+            priority_inversions: self
+                .priority_inversions
+                .into_iter()
+                .filter(|i| tasks.contains(&i.blocked.0))
+                .collect(),
+            worst_case_blocking: self
+                .worst_case_blocking
+                .into_iter()
+                .filter(|b| tasks.contains(&b.id))
+                .collect(),
+            tardiness: self
+                .tardiness
+                .into_iter()
+                .filter(|t| tasks.contains(&t.id))
+                .collect(),
+            timeline: self
+                .timeline
+                .into_iter()
+                .map(|slot| match slot {
+                    Slot::Job(id, iteration) if !selected.contains(&(id, iteration)) => {
+                        Slot::Job(0, 0)
+                    }
+                    slot => slot,
+                })
+                .collect(),
+            ..self
         }
     }
 }
 
-#[derive(Serialize)]
-#[serde(rename_all = "kebab-case")]
-pub struct Report {
-    jobs: Vec<JobReport>,
-    timeline: Vec<(usize, usize)>,
-}
-
 impl JobList {
     pub fn timeline(&self, to: usize) -> Vec<(usize, usize)> {
         let mut timeline = vec![(0, 0); to];
         for job in self.jobs.iter() {
             for run in &job.log {
-                for cycle in run.0..run.1 {
-                    timeline[cycle] = (job.id, job.iteration)
+                for slot in &mut timeline[run.0..run.1] {
+                    *slot = (job.id, job.iteration)
                 }
             }
         }
         timeline
     }
 
+    /// longest total blocking of a single job, for every task that got blocked
+    fn worst_case_blocking(&self) -> Vec<TaskBlocking> {
+        let mut blocking: BTreeMap<usize, usize> = BTreeMap::new();
+        for job in self.jobs.iter().filter(|j| !j.blocked.is_empty()) {
+            let blocked: usize = job.blocked.iter().map(|(s, e)| e - s).sum();
+            let worst = blocking.entry(job.id).or_default();
+            *worst = blocked.max(*worst);
+        }
+        blocking
+            .into_iter()
+            .map(|(id, worst_case_blocking)| TaskBlocking {
+                id,
+                worst_case_blocking,
+            })
+            .collect()
+    }
+
+    /// tardiness of the completed jobs, for every periodic task
+    fn tardiness(&self) -> Vec<TaskTardiness> {
+        let mut tardiness: BTreeMap<usize, TaskTardiness> = BTreeMap::new();
+        for job in self
+            .jobs
+            .iter()
+            .filter(|j| matches!(j.kind, JobKind::Periodic))
+        {
+            let task = tardiness.entry(job.id).or_insert_with(|| TaskTardiness {
+                id: job.id,
+                ..Default::default()
+            });
+            let late = job.tardiness().unwrap_or(0);
+            if late > 0 || job.missed() {
+                task.deadline_misses += 1;
+            }
+            task.max_tardiness = late.max(task.max_tardiness);
+            task.total_tardiness += late;
+        }
+        tardiness.into_values().collect()
+    }
+
     pub fn report(&self, to: usize) -> Report {
+        let (periodic, aperiodic): (Vec<&Job>, Vec<&Job>) = self
+            .jobs
+            .iter()
+            .partition(|j| matches!(j.kind, JobKind::Periodic));
         Report {
-            jobs: self
-                .jobs
-                .iter()
-                .map(|j| JobReport::from(j.clone()))
+            jobs: periodic.into_iter().map(JobReport::from).collect(),
+            aperiodic_jobs: aperiodic
+                .into_iter()
+                .map(AperiodicJobReport::from)
                 .collect(),
-            timeline: self.timeline(to),
+            servers: self.servers.iter().map(ServerReport::from).collect(),
+            mode_switches: self.mode_switches.clone(),
+            priority_inversions: self.priority_inversions.clone(),
+            worst_case_blocking: self.worst_case_blocking(),
+            tardiness: self.tardiness(),
+            context_switches: self.context_switches,
+            overheads: self.overhead_log.clone(),
+            total_overhead: self.overhead_log.iter().map(|o| o.to - o.from).sum(),
+            frequencies: self.frequencies.clone(),
+            energy: self.dvfs.as_ref().map(|d| d.energy(&self.frequencies, to)),
+            resolution: None,
+            timeline: self.slots(to),
+        }
+    }
+
+    /// the timeline up to `to`, with the overheads marked
+    fn slots(&self, to: usize) -> Vec<Slot> {
+        let mut slots: Vec<Slot> = self
+            .timeline(to)
+            .into_iter()
+            .map(|(id, iteration)| Slot::Job(id, iteration))
+            .collect();
+        for overhead in &self.overhead_log {
+            slots[overhead.from..overhead.to.min(to)].fill(Slot::Overhead);
         }
+        slots
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Task;
+    use crate::{
+        schedule_dags, Criticality, DagTask, DeadlineMonotonic, ExecutionTime, Fifo, FixedPriority,
+        Llf, OperatingPoint, RateMonotonic, ServerKind, Task, TaskList, TieBreak, Time,
+    };
 
     #[test]
     fn smoke() {
@@ -265,4 +2203,950 @@ mod tests {
         jobs.schedule();
         assert_eq!(timeline, jobs.timeline(24));
     }
+
+    #[test]
+    fn overloaded_ties() {
+        // 2 expires at 5 while waiting, then the jobs due at 10 tie
+        let mut jobs = TaskList::from(vec![
+            Task::new(1, 3, 5),
+            Task::new(2, 2, 5),
+            Task::new(3, 7, 15),
+            Task::new(4, 2, 5),
+        ])
+        .jobs_till_hyperperiod();
+        jobs.schedule();
+        let mut timeline = vec![(0, 0); 15];
+        timeline[0..3].fill((1, 0));
+        timeline[3..5].fill((4, 0));
+        timeline[5..7].fill((4, 1));
+        timeline[7..10].fill((1, 1));
+        assert_eq!(jobs.timeline(15), timeline);
+    }
+
+    #[test]
+    fn constrained_deadline() {
+        let task = Task::new(1, 2, 10).with_deadline(4);
+        let jobs = task.jobs_till(30);
+        let deadlines: Vec<usize> = jobs.jobs.iter().map(|j| j.deadline).collect();
+        assert_eq!(deadlines, vec![4, 14, 24]);
+    }
+
+    #[test]
+    fn arbitrary_deadline() {
+        // t2 has a constrained deadline and delays t1.0 past the release of t1.1
+        let t1 = Task::new(1, 2, 4).with_deadline(8);
+        let t2 = Task::new(2, 3, 8).with_deadline(4);
+        let mut jobs = JobList::new();
+        jobs.join(t1.jobs_till(16)).join(t2.jobs_till(16));
+        let timeline = vec![
+            (2, 0),
+            (2, 0),
+            (2, 0),
+            (1, 0),
+            (1, 0),
+            (1, 1),
+            (1, 1),
+            (0, 0),
+        ];
+
+        jobs.schedule();
+        assert_eq!(timeline, jobs.timeline(16)[..8]);
+    }
+
+    #[test]
+    fn offset() {
+        let task = Task::new(1, 2, 10).with_offset(3);
+        let jobs = task.jobs_till(30);
+        let arrivals: Vec<usize> = jobs.jobs.iter().map(|j| j.arrival_time).collect();
+        assert_eq!(arrivals, vec![3, 13, 23]);
+    }
+
+    #[test]
+    fn asynchronous() {
+        let t1 = Task::new(1, 2, 4);
+        let t2 = Task::new(2, 2, 4).with_offset(1);
+        let mut jobs = TaskList::from(vec![t1, t2]).jobs_till_hyperperiod();
+        let timeline = vec![
+            (1, 0),
+            (1, 0),
+            (2, 0),
+            (2, 0),
+            (1, 1),
+            (1, 1),
+            (2, 1),
+            (2, 1),
+        ];
+
+        jobs.schedule();
+        assert_eq!(timeline, jobs.timeline(9)[..8]);
+    }
+
+    #[test]
+    fn sporadic() {
+        let task = Task::sporadic(1, 2, 10, 5);
+        let arrivals: Vec<usize> = task
+            .jobs_till(1000)
+            .jobs
+            .iter()
+            .map(|j| j.arrival_time)
+            .collect();
+        for pair in arrivals.windows(2) {
+            assert!(pair[1] - pair[0] >= 10);
+            assert!(pair[1] - pair[0] <= 15);
+        }
+
+        let replica: Vec<usize> = task
+            .clone()
+            .jobs_till(1000)
+            .jobs
+            .iter()
+            .map(|j| j.arrival_time)
+            .collect();
+        assert_eq!(arrivals, replica);
+
+        let reseeded: Vec<usize> = task
+            .with_seed(42)
+            .jobs_till(1000)
+            .jobs
+            .iter()
+            .map(|j| j.arrival_time)
+            .collect();
+        assert_ne!(arrivals, reseeded);
+    }
+
+    #[test]
+    fn sporadic_without_delay_is_periodic() {
+        let periodic = Task::new(1, 2, 10).jobs_till(100);
+        let sporadic = Task::sporadic(1, 2, 10, 0).jobs_till(100);
+        let arrivals =
+            |jobs: &JobList| -> Vec<usize> { jobs.jobs.iter().map(|j| j.arrival_time).collect() };
+        assert_eq!(arrivals(&periodic), arrivals(&sporadic));
+    }
+
+    #[test]
+    fn aperiodic_background() {
+        let task = Task::new(1, 2, 4);
+        let mut jobs = task.jobs_till(12);
+        jobs.push_aperiodic(AperiodicJob::new(7, 1, 3).with_deadline(6));
+        jobs.push_aperiodic(AperiodicJob::new(8, 2, 1));
+        let timeline = vec![
+            (1, 0),
+            (1, 0),
+            (7, 0),
+            (7, 0),
+            (1, 1),
+            (1, 1),
+            (7, 0),
+            (8, 0),
+        ];
+
+        jobs.schedule();
+        assert_eq!(timeline, jobs.timeline(12)[..8]);
+
+        let report = jobs.report(12);
+        assert_eq!(report.jobs.len(), 2);
+        let responses: Vec<(usize, Option<usize>)> = report
+            .aperiodic_jobs
+            .iter()
+            .map(|j| (j.id, j.response_time))
+            .collect();
+        assert_eq!(responses, vec![(7, Some(6)), (8, Some(6))]);
+    }
+
+    #[test]
+    fn execution_time() {
+        let task = Task::new(1, 4, 10).with_execution_time(ExecutionTime::Uniform { bcet: 1 });
+        let mut jobs = task.jobs_till(1000);
+        let demands: Vec<usize> = jobs.jobs.iter().map(|j| j.remaining).collect();
+        assert!(demands.iter().all(|d| (1..=4).contains(d)));
+        assert!(demands.iter().any(|d| *d < 4));
+        assert_eq!(
+            demands,
+            task.clone()
+                .jobs_till(1000)
+                .jobs
+                .iter()
+                .map(|j| j.remaining)
+                .collect::<Vec<usize>>()
+        );
+
+        jobs.schedule();
+        let report = jobs.report(1000);
+        for (job, demand) in report.jobs.iter().zip(demands) {
+            assert_eq!(job.wcet, 4);
+            assert_eq!(job.consumed, demand);
+        }
+    }
+
+    #[test]
+    fn edf_vd() {
+        let hi = Task::new(1, 2, 10)
+            .with_hi_wcet(6)
+            .with_execution_time(ExecutionTime::Histogram(vec![(6, 1.0)]));
+        let lo = Task::new(2, 5, 10);
+        let tasklist = TaskList::from(vec![hi, lo]);
+        let factor = tasklist.edf_vd_factor().unwrap();
+        assert!((factor - 0.4).abs() < 1e-6);
+
+        let mut jobs = tasklist.jobs_till_hyperperiod().with_edf_vd(factor);
+        // an arrival at 10 lets the simulation cover the whole first period
+        jobs.push(Job::new(3, 0, 10, 1, 20));
+        let timeline = vec![
+            (1, 0),
+            (1, 0),
+            (1, 0),
+            (1, 0),
+            (1, 0),
+            (1, 0),
+            (0, 0),
+            (0, 0),
+            (0, 0),
+            (0, 0),
+        ];
+
+        jobs.schedule();
+        assert_eq!(timeline, jobs.timeline(10));
+        let switches: Vec<(usize, Criticality)> = jobs
+            .mode_switches
+            .iter()
+            .map(|s| (s.time, s.mode))
+            .collect();
+        assert_eq!(switches, vec![(2, Criticality::Hi), (6, Criticality::Lo)]);
+        let lo_job = jobs.jobs.iter().find(|j| j.id == 2).unwrap();
+        assert!(matches!(lo_job.status, JobStatus::Dropped));
+    }
+
+    #[test]
+    fn stack_resource_policy() {
+        // t2 holds the resource from 1 to 3 and blocks t1 arriving at 2
+        let t1 = Task::new(1, 2, 10)
+            .with_offset(2)
+            .with_critical_section(1, 0, 1);
+        let t2 = Task::new(2, 4, 20).with_critical_section(1, 1, 2);
+        let mut jobs = JobList::new();
+        jobs.join(t1.jobs_till(20)).join(t2.jobs_till(20));
+        let timeline = vec![(2, 0), (2, 0), (2, 0), (1, 0), (1, 0), (2, 0), (0, 0)];
+
+        jobs.schedule();
+        assert_eq!(timeline, jobs.timeline(20)[..7]);
+        let report = jobs.report(20);
+        assert_eq!(report.priority_inversions.len(), 1);
+        let inversion = &report.priority_inversions[0];
+        assert_eq!((inversion.from, inversion.to), (2, 3));
+        assert_eq!((inversion.running, inversion.blocked), ((2, 0), (1, 0)));
+        assert_eq!(report.worst_case_blocking.len(), 1);
+        assert_eq!(report.worst_case_blocking[0].id, 1);
+        assert_eq!(report.worst_case_blocking[0].worst_case_blocking, 1);
+    }
+
+    #[test]
+    fn preemption_outside_critical_section() {
+        // t2 has already left its critical section when t1 arrives
+        let t1 = Task::new(1, 2, 10)
+            .with_offset(2)
+            .with_critical_section(1, 0, 1);
+        let t2 = Task::new(2, 4, 20).with_critical_section(1, 0, 1);
+        let mut jobs = JobList::new();
+        jobs.join(t1.jobs_till(20)).join(t2.jobs_till(20));
+        let timeline = vec![(2, 0), (2, 0), (1, 0), (1, 0), (2, 0), (2, 0), (0, 0)];
+
+        jobs.schedule();
+        assert_eq!(timeline, jobs.timeline(20)[..7]);
+        assert!(jobs.report(20).priority_inversions.is_empty());
+    }
+
+    #[test]
+    fn preemption_modes() {
+        let t1 = Task::new(1, 1, 4).with_offset(1);
+        let t2 = Task::new(2, 4, 10).with_non_preemptive_region(2);
+        let schedule = |preemption: Preemption| {
+            let mut jobs = JobList::new().with_preemption(preemption);
+            jobs.join(t1.jobs_till(10)).join(t2.jobs_till(10));
+            jobs.schedule();
+            jobs.timeline(10)[..5].to_vec()
+        };
+
+        assert_eq!(
+            schedule(Preemption::Preemptive),
+            vec![(2, 0), (1, 0), (2, 0), (2, 0), (2, 0)]
+        );
+        assert_eq!(
+            schedule(Preemption::NonPreemptive),
+            vec![(2, 0), (2, 0), (2, 0), (2, 0), (1, 0)]
+        );
+        assert_eq!(
+            schedule(Preemption::Limited),
+            vec![(2, 0), (2, 0), (1, 0), (2, 0), (2, 0)]
+        );
+    }
+
+    #[test]
+    fn filter_report() {
+        let t1 = Task::new(1, 1, 4).with_name("brake").with_label("control");
+        let t2 = Task::new(2, 1, 4)
+            .with_name("logger")
+            .with_attribute("subsystem", "logging");
+        let mut jobs = JobList::new();
+        jobs.join(t1.jobs_till(8)).join(t2.jobs_till(8));
+        jobs.schedule();
+
+        let report = jobs
+            .report(4)
+            .filter(&Selector::new().with_label("control"));
+        assert_eq!(report.jobs.len(), 1);
+        assert_eq!(report.jobs[0].metadata.name.as_deref(), Some("brake"));
+
+        let report = jobs
+            .report(4)
+            .filter(&Selector::new().with_attribute("subsystem", "logging"));
+        assert_eq!(report.jobs.len(), 1);
+        assert_eq!(report.jobs[0].id, 2);
+
+        // the other sections only keep the selected tasks too
+        let t3 = Task::new(3, 3, 8).with_deadline(4).with_label("control");
+        let server = Task::server(5, ServerKind::Polling, 1, 8).with_name("operator");
+        let mut jobs = JobList::new();
+        jobs.join(t1.jobs_till(8))
+            .join(t2.jobs_till(8))
+            .join(t3.jobs_till(8))
+            .join(server.jobs_till(8));
+        jobs.push_aperiodic(AperiodicJob::new(9, 0, 1).with_server(5));
+        jobs.push_aperiodic(AperiodicJob::new(7, 0, 1));
+        jobs.schedule_until(8);
+        let full = jobs.report(8);
+        assert_eq!(full.tardiness.len(), 3);
+        assert_eq!(full.aperiodic_jobs.len(), 2);
+        assert!([2, 9].iter().all(|id| full
+            .timeline
+            .iter()
+            .any(|slot| matches!(slot, Slot::Job(i, _) if i == id))));
+        let report = jobs
+            .report(8)
+            .filter(&Selector::new().with_label("control"));
+        assert!(report.tardiness.iter().all(|t| t.id != 2));
+        assert!(report.worst_case_blocking.iter().all(|b| b.id != 2));
+        assert!(report.aperiodic_jobs.is_empty() && report.servers.is_empty());
+        assert!(report
+            .timeline
+            .iter()
+            .all(|slot| !matches!(slot, Slot::Job(2 | 9, _))));
+        assert_eq!(
+            report
+                .timeline
+                .iter()
+                .filter(|slot| matches!(slot, Slot::Job(3, _)))
+                .count(),
+            full.timeline
+                .iter()
+                .filter(|slot| matches!(slot, Slot::Job(3, _)))
+                .count()
+        );
+        let report = jobs
+            .report(8)
+            .filter(&Selector::new().with_name("operator"));
+        assert_eq!(report.servers.len(), 1);
+        assert_eq!(report.aperiodic_jobs[0].id, 9);
+    }
+
+    #[test]
+    fn precedence_logs() {
+        // 2 runs on its own processor while 3 completes on the other one
+        let dag = DagTask::new(1, 10, vec![(1, 2), (2, 3), (3, 1)], vec![(1, 2), (1, 3)]).unwrap();
+        let tasks = dag.tasks();
+        let mut joblists = vec![
+            TaskList::from(vec![tasks[0].clone(), tasks[2].clone()]).jobs_till_hyperperiod(),
+            TaskList::from(vec![tasks[1].clone()]).jobs_till_hyperperiod(),
+        ];
+        schedule_dags(&mut joblists, &[dag], 10);
+        assert_eq!(joblists[1].report(10).jobs[0].log, vec![(2, 5)]);
+    }
+
+    #[test]
+    fn late_precedence_release() {
+        // the successor keeps its arrival, and is released past its deadline
+        let dag = DagTask::new(1, 10, vec![(1, 12), (2, 1)], vec![(1, 2)]).unwrap();
+        let mut joblists = vec![TaskList::from(dag.tasks())
+            .jobs_till(10)
+            .with_deadline_miss(DeadlineMiss::ContinueLate)];
+        schedule_dags(&mut joblists, &[dag], 20);
+        let report = joblists[0].report(20);
+        let successor = &report.jobs[1];
+        assert_eq!((successor.id, successor.arrival_time), (2, 0));
+        assert_eq!(successor.precedence_release, Some(12));
+        assert_eq!(successor.tardiness, Some(3));
+    }
+
+    #[test]
+    fn split_pieces() {
+        // 3 is split in a piece of 4 on the first processor and one of 2 on the second
+        let tasklist = TaskList::from(vec![
+            Task::new(1, 6, 10),
+            Task::new(2, 6, 10),
+            Task::new(3, 6, 10),
+        ]);
+        let mut joblists: Vec<JobList> = tasklist
+            .semi_partitioned(2)
+            .unwrap()
+            .iter()
+            .map(|p| p.jobs_till(30))
+            .collect();
+        schedule_dags(&mut joblists, &[], 30);
+        let reports = split_reports(&joblists);
+        assert_eq!(
+            reports.iter().map(|r| r.finish_time).collect::<Vec<_>>(),
+            vec![Some(8), Some(18), Some(28)]
+        );
+
+        // the second piece never runs once the first one misses its deadline
+        let mut joblists = vec![JobList::new(), JobList::new()];
+        joblists[0].push(Job::new(1, 0, 0, 2, 2));
+        joblists[0].push(Job::new(3, 0, 0, 4, 4).with_piece(Some((0, 2))));
+        joblists[1].push(Job::new(3, 0, 4, 2, 10).with_piece(Some((1, 2))));
+        schedule_dags(&mut joblists, &[], 10);
+        assert!(joblists[1].report(10).jobs[0].log.is_empty());
+        let reports = split_reports(&joblists);
+        assert!(matches!(reports[0].status, JobStatus::DeadlineExceeded));
+        assert_eq!(reports[0].finish_time, None);
+    }
+
+    #[test]
+    fn report_in_unit() {
+        let resolution = Resolution::new(Time::us(250));
+        let task = Task::from_time(1, Time::us(500), Time::ms(1), &resolution).unwrap();
+        let mut jobs = task.jobs_till(8);
+        jobs.schedule();
+
+        let report = jobs.report(8).in_unit(&resolution, TimeUnit::Us).unwrap();
+        assert_eq!(report.jobs[0].wcet, 500);
+        assert_eq!(report.jobs[0].deadline, 1000);
+        assert_eq!(report.jobs[0].log, vec![(0, 500)]);
+        assert_eq!(report.timeline.len(), 8);
+        assert!(jobs.report(8).in_unit(&resolution, TimeUnit::Ms).is_err());
+    }
+
+    #[test]
+    fn fixed_priority_policies() {
+        // t1 has the shorter deadline but the longer period
+        let t1 = Task::new(1, 2, 10).with_deadline(3);
+        let t2 = Task::new(2, 2, 4);
+        let schedule = |jobs: JobList| {
+            let mut jobs = jobs;
+            jobs.join(t1.jobs_till(8)).join(t2.jobs_till(8));
+            jobs.schedule();
+            jobs.timeline(8)[..4].to_vec()
+        };
+
+        assert_eq!(
+            schedule(JobList::new()),
+            vec![(1, 0), (1, 0), (2, 0), (2, 0)]
+        );
+        assert_eq!(
+            schedule(JobList::new().with_policy(DeadlineMonotonic)),
+            vec![(1, 0), (1, 0), (2, 0), (2, 0)]
+        );
+        // t1 gets aborted at its deadline
+        assert_eq!(
+            schedule(JobList::new().with_policy(RateMonotonic)),
+            vec![(2, 0), (2, 0), (1, 0), (0, 0)]
+        );
+    }
+
+    #[test]
+    fn fifo() {
+        let t1 = Task::new(1, 3, 10);
+        let t2 = Task::new(2, 1, 10).with_deadline(4).with_offset(1);
+        let schedule = |jobs: JobList| {
+            let mut jobs = jobs;
+            jobs.join(t1.jobs_till(20)).join(t2.jobs_till(20));
+            jobs.schedule();
+            jobs.timeline(20)[..4].to_vec()
+        };
+
+        assert_eq!(
+            schedule(JobList::new()),
+            vec![(1, 0), (2, 0), (1, 0), (1, 0)]
+        );
+        assert_eq!(
+            schedule(JobList::new().with_policy(Fifo)),
+            vec![(1, 0), (1, 0), (1, 0), (2, 0)]
+        );
+    }
+
+    #[test]
+    fn deadline_passed_before_running() {
+        // t2 has the shorter period and runs past the deadline of t1
+        let t1 = Task::new(1, 1, 10).with_deadline(2);
+        let t2 = Task::new(2, 3, 5);
+        let mut jobs = JobList::new().with_policy(RateMonotonic);
+        jobs.join(t1.jobs_till(20)).join(t2.jobs_till(20));
+        jobs.schedule();
+        assert_eq!(
+            jobs.timeline(20)[..4].to_vec(),
+            vec![(2, 0), (2, 0), (2, 0), (0, 0)]
+        );
+        assert!(matches!(
+            jobs.jobs.iter().find(|j| j.id == 1).unwrap().status,
+            JobStatus::DeadlineExceeded
+        ));
+    }
+
+    #[test]
+    fn fixed_priority() {
+        // the task with the later deadline has the higher priority
+        let t1 = Task::new(1, 2, 4).with_priority(1);
+        let t2 = Task::new(2, 2, 8).with_priority(0);
+        let mut jobs = JobList::new().with_policy(FixedPriority);
+        jobs.join(t1.jobs_till(16)).join(t2.jobs_till(16));
+        jobs.schedule();
+        assert_eq!(
+            jobs.timeline(16)[..8].to_vec(),
+            vec![
+                (2, 0),
+                (2, 0),
+                (1, 0),
+                (1, 0),
+                (1, 1),
+                (1, 1),
+                (0, 0),
+                (0, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn least_laxity_first() {
+        let t1 = Task::new(1, 3, 6);
+        let t2 = Task::new(2, 3, 6);
+        let schedule = |jobs: JobList| {
+            let mut jobs = jobs;
+            jobs.join(t1.jobs_till(12)).join(t2.jobs_till(12));
+            jobs.schedule();
+            let report = jobs.report(12);
+            (jobs.timeline(12)[..6].to_vec(), report.context_switches)
+        };
+
+        assert_eq!(
+            schedule(JobList::new()),
+            (vec![(1, 0), (1, 0), (1, 0), (2, 0), (2, 0), (2, 0)], 2)
+        );
+        // equal laxities make LLF thrash between the jobs
+        let llf = Llf::new().with_tie_break(TieBreak::LowestId);
+        assert_eq!(
+            schedule(JobList::new().with_policy(llf)),
+            (vec![(1, 0), (2, 0), (1, 0), (2, 0), (1, 0), (2, 0)], 6)
+        );
+        assert_eq!(
+            schedule(JobList::new().with_policy(llf.with_quantum(3))),
+            (vec![(1, 0), (1, 0), (1, 0), (2, 0), (2, 0), (2, 0)], 2)
+        );
+        let mllf = Llf::modified().with_tie_break(TieBreak::LowestId);
+        assert_eq!(
+            schedule(JobList::new().with_policy(mllf)),
+            (vec![(1, 0), (1, 0), (1, 0), (2, 0), (2, 0), (2, 0)], 2)
+        );
+    }
+
+    #[test]
+    fn deadline_miss_policies() {
+        // t2 starts at 1 and can't complete by 4, t3 preempts it at 2
+        let t1 = Task::new(1, 1, 10).with_deadline(1);
+        let t2 = Task::new(2, 4, 10).with_deadline(4);
+        let t3 = Task::new(3, 1, 10).with_deadline(1).with_offset(2);
+        let schedule = |deadline_miss: DeadlineMiss| {
+            let mut jobs = JobList::new().with_deadline_miss(deadline_miss);
+            jobs.join(t1.jobs_till(20))
+                .join(t2.jobs_till(20))
+                .join(t3.jobs_till(20));
+            jobs.schedule();
+            jobs
+        };
+        let status = |jobs: &JobList, iteration: usize| {
+            let job = jobs
+                .jobs
+                .iter()
+                .find(|j| j.id == 2 && j.iteration == iteration)
+                .unwrap();
+            (job.status.clone(), job.tardiness())
+        };
+
+        let jobs = schedule(DeadlineMiss::AbortPredictively);
+        assert_eq!(
+            jobs.timeline(20)[..6].to_vec(),
+            vec![(1, 0), (2, 0), (3, 0), (0, 0), (0, 0), (0, 0)]
+        );
+        assert!(matches!(
+            status(&jobs, 0),
+            (JobStatus::DeadlineExceeded, None)
+        ));
+
+        let jobs = schedule(DeadlineMiss::AbortAtDeadline);
+        assert_eq!(
+            jobs.timeline(20)[..6].to_vec(),
+            vec![(1, 0), (2, 0), (3, 0), (2, 0), (0, 0), (0, 0)]
+        );
+        assert!(matches!(
+            status(&jobs, 0),
+            (JobStatus::DeadlineExceeded, None)
+        ));
+
+        let late = vec![(1, 0), (2, 0), (3, 0), (2, 0), (2, 0), (2, 0), (0, 0)];
+        let jobs = schedule(DeadlineMiss::ContinueLate);
+        assert_eq!(jobs.timeline(20)[..7].to_vec(), late);
+        assert!(matches!(status(&jobs, 0), (JobStatus::Done, Some(2))));
+        let tardiness = jobs.tardiness();
+        assert_eq!(tardiness[1].id, 2);
+        assert_eq!(tardiness[1].deadline_misses, 1);
+        assert_eq!(tardiness[1].max_tardiness, 2);
+
+        let jobs = schedule(DeadlineMiss::SkipNext);
+        assert_eq!(jobs.timeline(20)[..7].to_vec(), late);
+        assert!(matches!(status(&jobs, 1), (JobStatus::Skipped, None)));
+    }
+
+    #[test]
+    fn constant_bandwidth_server() {
+        let t1 = Task::new(1, 2, 4);
+        let server = Task::server(5, ServerKind::Cbs, 1, 4);
+        assert_eq!(server.utilization(), 0.25);
+        let schedule = |execution: usize| {
+            let mut jobs = JobList::new();
+            jobs.join(t1.jobs_till(12)).join(server.jobs_till(12));
+            jobs.push_aperiodic(AperiodicJob::new(9, 0, execution).with_server(5));
+            jobs.schedule();
+            jobs
+        };
+
+        // the budget is recharged with a postponed deadline on exhaustion
+        let jobs = schedule(3);
+        assert_eq!(
+            jobs.timeline(12)[..8].to_vec(),
+            vec![
+                (1, 0),
+                (1, 0),
+                (9, 0),
+                (9, 0),
+                (1, 1),
+                (1, 1),
+                (9, 0),
+                (0, 0)
+            ]
+        );
+        let report = jobs.report(12);
+        assert_eq!(report.aperiodic_jobs[0].server, Some(5));
+        assert_eq!(report.aperiodic_jobs[0].response_time, Some(7));
+
+        // a request overrunning the server doesn't hurt the hard task
+        let jobs = schedule(100);
+        assert!(jobs
+            .jobs
+            .iter()
+            .filter(|j| j.id == 1)
+            .all(|j| matches!(j.status, JobStatus::Done)));
+    }
+
+    #[test]
+    fn fixed_priority_servers() {
+        let t1 = Task::new(1, 2, 6).with_priority(1);
+        let schedule = |kind: ServerKind| {
+            let server = Task::server(5, kind, 2, 4).with_priority(0);
+            let mut jobs = JobList::new().with_policy(FixedPriority);
+            jobs.join(t1.jobs_till(12)).join(server.jobs_till(12));
+            jobs.push_aperiodic(AperiodicJob::new(9, 1, 2).with_server(5));
+            jobs.schedule();
+            let report = jobs.report(12);
+            (
+                jobs.timeline(12)[..6].to_vec(),
+                report.aperiodic_jobs[0].response_time,
+                report.servers[0].budget.clone(),
+            )
+        };
+
+        // the request missed the poll at 0
+        assert_eq!(
+            schedule(ServerKind::Polling),
+            (
+                vec![(1, 0), (1, 0), (0, 0), (0, 0), (9, 0), (9, 0)],
+                Some(5),
+                vec![(0, 0), (4, 2), (6, 0)]
+            )
+        );
+        assert_eq!(
+            schedule(ServerKind::Deferrable),
+            (
+                vec![(1, 0), (9, 0), (9, 0), (1, 0), (0, 0), (0, 0)],
+                Some(2),
+                vec![(0, 2), (3, 0), (4, 2)]
+            )
+        );
+        assert_eq!(
+            schedule(ServerKind::Sporadic),
+            (
+                vec![(1, 0), (9, 0), (9, 0), (1, 0), (0, 0), (0, 0)],
+                Some(2),
+                vec![(0, 2), (3, 0), (5, 2)]
+            )
+        );
+    }
+
+    #[test]
+    fn schedule_until() {
+        let mut tasks = TaskList::from(vec![
+            Task::new(1, 2, 6),
+            Task::new(2, 2, 8),
+            Task::new(3, 3, 12),
+        ]);
+        let mut jobs = tasks.jobs_till(24);
+        jobs.schedule_until(24);
+        // the ready jobs run past the last arrival at 18
+        assert_eq!(
+            jobs.timeline(24)[16..].to_vec(),
+            vec![
+                (3, 1),
+                (2, 2),
+                (2, 2),
+                (1, 3),
+                (1, 3),
+                (0, 0),
+                (0, 0),
+                (0, 0)
+            ]
+        );
+        assert!(jobs
+            .jobs
+            .iter()
+            .all(|j| matches!(j.status, JobStatus::Done)));
+
+        // a polling server replenishes forever, the horizon bounds it
+        tasks.push(Task::server(9, ServerKind::Polling, 1, 4));
+        let mut jobs = tasks.jobs_till(24);
+        jobs.schedule_until(18);
+        let status = |id: usize, iteration: usize| {
+            let job = jobs
+                .jobs
+                .iter()
+                .find(|j| j.id == id && j.iteration == iteration)
+                .unwrap();
+            job.status.clone()
+        };
+        assert!(matches!(status(2, 2), JobStatus::Unfinished));
+        // released at the horizon
+        assert!(matches!(status(1, 3), JobStatus::Ready));
+    }
+
+    #[test]
+    fn overhead_ties() {
+        // the jobs of equal deadline don't take the processor from each other
+        // at the end of every context switch
+        let t1 = Task::new(1, 2, 10);
+        let t2 = Task::new(2, 2, 10);
+        let mut jobs = JobList::new().with_overheads(Overheads::new().with_context_switch(1));
+        jobs.join(t1.jobs_till(10)).join(t2.jobs_till(10));
+        jobs.schedule_until(10);
+        let report = jobs.report(10);
+        assert_eq!(report.total_overhead, 2);
+        assert_eq!(
+            jobs.timeline(10)[..6],
+            [(0, 0), (1, 0), (1, 0), (0, 0), (2, 0), (2, 0)]
+        );
+    }
+
+    #[test]
+    fn overheads() {
+        let t1 = Task::new(1, 1, 12).with_deadline(4).with_offset(3);
+        let t2 = Task::new(2, 3, 12);
+        let overheads = Overheads::new()
+            .with_context_switch(1)
+            .with_release(1)
+            .with_completion(1);
+        let mut jobs = JobList::new().with_overheads(overheads);
+        jobs.join(t1.jobs_till(12)).join(t2.jobs_till(12));
+        jobs.schedule_until(12);
+        let report = jobs.report(12);
+        let overhead = Slot::Overhead;
+        assert_eq!(
+            report.timeline,
+            vec![
+                overhead,
+                overhead,
+                Slot::Job(2, 0),
+                overhead,
+                overhead,
+                Slot::Job(1, 0),
+                overhead,
+                overhead,
+                Slot::Job(2, 0),
+                Slot::Job(2, 0),
+                overhead,
+                Slot::Job(0, 0),
+            ]
+        );
+        let kinds: Vec<(OverheadKind, usize, usize)> = report
+            .overheads
+            .iter()
+            .map(|o| (o.kind, o.from, o.to))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (OverheadKind::Release, 0, 1),
+                (OverheadKind::ContextSwitch, 1, 2),
+                (OverheadKind::Release, 3, 4),
+                (OverheadKind::ContextSwitch, 4, 5),
+                (OverheadKind::Completion, 6, 7),
+                (OverheadKind::ContextSwitch, 7, 8),
+                (OverheadKind::Completion, 10, 11),
+            ]
+        );
+        assert_eq!(report.total_overhead, 7);
+        assert_eq!(report.context_switches, 3);
+        let preemptions: Vec<(usize, usize)> =
+            report.jobs.iter().map(|j| (j.id, j.preemptions)).collect();
+        assert!(preemptions.contains(&(2, 1)) && preemptions.contains(&(1, 0)));
+    }
+
+    #[test]
+    fn dvfs() {
+        let t1 = Task::new(1, 1, 4);
+        // t2 only takes half of its wcet
+        let t2 = Task::new(2, 2, 8).with_execution_time(ExecutionTime::Histogram(vec![(1, 1.0)]));
+        let dvfs = Dvfs::new(vec![
+            OperatingPoint::new(250, 0.8),
+            OperatingPoint::new(375, 0.9),
+            OperatingPoint::new(500, 1.0),
+            OperatingPoint::new(1000, 1.2),
+        ]);
+        let schedule = |governor: Governor| {
+            let mut jobs = JobList::new().with_dvfs(dvfs.clone().with_governor(governor));
+            jobs.join(t1.jobs_till(8)).join(t2.jobs_till(8));
+            jobs.schedule_until(8);
+            assert!(jobs
+                .jobs
+                .iter()
+                .all(|j| matches!(j.status, JobStatus::Done)));
+            let report = jobs.report(8);
+            let frequencies: Vec<(usize, usize, usize)> = report
+                .frequencies
+                .iter()
+                .map(|s| (s.from, s.to, s.frequency))
+                .collect();
+            (frequencies, report.energy().unwrap())
+        };
+
+        let (frequencies, energy) = schedule(Governor::Maximum);
+        assert_eq!(frequencies, vec![(0, 2, 1000), (4, 5, 1000)]);
+        assert!((energy - 3.0 * 1000.0 * 1.44).abs() < 1e-6);
+
+        // the task set has a utilization of 1/2
+        let (frequencies, energy) = schedule(Governor::StaticSlowdown);
+        assert_eq!(frequencies, vec![(0, 6, 500)]);
+        assert!((energy - 6.0 * 500.0).abs() < 1e-6);
+
+        // t2 completing early leaves a utilization of 3/8
+        let (frequencies, energy) = schedule(Governor::CycleConserving);
+        assert_eq!(frequencies, vec![(0, 4, 500), (4, 7, 375)]);
+        assert!((energy - (4.0 * 500.0 + 3.0 * 375.0 * 0.81)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn heterogeneous() {
+        let t1 = Task::new(1, 2, 10);
+        let t2 = Task::new(2, 3, 10).with_deadline(9);
+        let platform = Platform::uniform(vec![1.0, 0.5]).with_wcets(&t2, vec![3, 4]);
+        let timeline = |index: usize| {
+            let mut jobs = JobList::new().with_platform(platform.clone(), index);
+            jobs.join(t1.jobs_till(10)).join(t2.jobs_till(10));
+            jobs.schedule_until(10);
+            jobs.timeline(10)
+        };
+        assert_eq!(
+            timeline(0),
+            [[(2, 0); 3].as_slice(), &[(1, 0); 2], &[(0, 0); 5]].concat()
+        );
+        // t1 runs at half speed and t2 takes 4 on the little core
+        assert_eq!(
+            timeline(1),
+            [[(2, 0); 4].as_slice(), &[(1, 0); 4], &[(0, 0); 2]].concat()
+        );
+    }
+
+    #[test]
+    fn critical_section_on_fast_core() {
+        // t2 leaves its section after 4 of execution, which takes 2 on the core
+        let t1 = Task::new(1, 2, 20)
+            .with_offset(1)
+            .with_deadline(5)
+            .with_critical_section(1, 0, 1);
+        let t2 = Task::new(2, 8, 20).with_critical_section(1, 0, 4);
+        let mut jobs = JobList::new().with_platform(Platform::uniform(vec![2.0]), 0);
+        jobs.join(t1.jobs_till(20)).join(t2.jobs_till(20));
+        jobs.schedule_until(20);
+        assert_eq!(
+            jobs.timeline(20)[..6].to_vec(),
+            vec![(2, 0), (2, 0), (1, 0), (2, 0), (2, 0), (0, 0)]
+        );
+    }
+
+    #[test]
+    fn equal_deadlines() {
+        let tasks = TaskList::from(vec![
+            Task::new(1, 4, 20),
+            Task::new(2, 4, 20),
+            Task::new(3, 1, 5).with_deadline(100),
+            Task::new(4, 1, 20).with_offset(3).with_deadline(100),
+            Task::new(5, 1, 50).with_offset(16).with_deadline(30),
+        ]);
+        let mut jobs = tasks.jobs_till(20);
+        jobs.schedule_until(20);
+        // jobs of equal deadline take turns at every decision
+        assert_eq!(
+            jobs.timeline(20)[..8].to_vec(),
+            vec![
+                (1, 0),
+                (1, 0),
+                (1, 0),
+                (2, 0),
+                (2, 0),
+                (1, 0),
+                (2, 0),
+                (2, 0)
+            ]
+        );
+        // released while idle
+        assert_eq!(jobs.timeline(20)[16], (5, 0));
+    }
+
+    #[test]
+    fn timer_tick() {
+        let tasks = TaskList::from(vec![
+            Task::new(1, 6, 20),
+            Task::new(2, 1, 10).with_offset(1),
+        ]);
+        let mut jobs = tasks.jobs_till(20);
+        jobs.schedule_until(20);
+        assert_eq!(
+            jobs.timeline(20)[..3].to_vec(),
+            vec![(1, 0), (2, 0), (1, 0)]
+        );
+
+        let mut jobs = tasks.jobs_till(20).with_timer_tick(4);
+        jobs.schedule_until(20);
+        let timeline = jobs.timeline(20);
+        // released and preempting at the next tick
+        assert_eq!(
+            timeline[..6].to_vec(),
+            vec![(1, 0), (1, 0), (1, 0), (1, 0), (2, 0), (1, 0)]
+        );
+        assert_eq!(timeline[11..13].to_vec(), vec![(0, 0), (2, 1)]);
+        let latency = |id: usize, iteration: usize| {
+            let job = jobs
+                .jobs
+                .iter()
+                .find(|j| j.id == id && j.iteration == iteration)
+                .unwrap();
+            job.release_latency
+        };
+        assert_eq!(latency(1, 0), Some(0));
+        assert_eq!(latency(2, 0), Some(3));
+        assert_eq!(latency(2, 1), Some(1));
+    }
 }
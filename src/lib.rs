@@ -1,7 +1,33 @@
-
+mod dag;
+mod dvfs;
+mod event;
+mod execution;
+mod global;
 mod job;
+mod metadata;
+mod overhead;
+mod platform;
+mod policy;
+mod priority;
+mod ready;
+mod server;
 mod task;
+mod time;
 mod uunifast;
 
+pub use dag::{schedule_dags, DagError, DagReport, DagTask};
+pub use dvfs::{Dvfs, Governor, OperatingPoint, PowerModel};
+pub use execution::ExecutionTime;
+pub use global::{schedule_global, schedule_global_on, GlobalJobReport, GlobalReport};
+pub use job::{split_reports, AperiodicJob, DeadlineMiss, Job, JobList, Preemption, SplitReport};
+pub use metadata::Selector;
+pub use overhead::Overheads;
+pub use platform::Platform;
+pub use policy::{
+    DeadlineMonotonic, Edf, Fifo, FixedPriority, Llf, RateMonotonic, SchedulingPolicy, TieBreak,
+};
+pub use priority::{Admission, ResponseTimeAnalysis, SchedulabilityTest};
+pub use server::ServerKind;
+pub use task::{Criticality, Task, TaskList};
+pub use time::{Resolution, Time, TimeError, TimeUnit};
 pub use uunifast::uunifast;
-pub use task::{Task,TaskList};
@@ -1,11 +1,26 @@
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
-use clap::{Parser, ValueEnum};
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches, Parser, ValueEnum};
 use rand::seq::SliceRandom;
 
+use scheduling::schedule_global_on;
+use scheduling::split_reports;
 use scheduling::uunifast;
+use scheduling::Admission;
+use scheduling::AperiodicJob;
+use scheduling::Overheads;
+use scheduling::Platform;
+use scheduling::Selector;
 use scheduling::Task;
 use scheduling::TaskList;
+use scheduling::{schedule_dags, DagTask};
+use scheduling::{DeadlineMonotonic, Edf, Fifo, FixedPriority, Llf, RateMonotonic};
+use scheduling::{Dvfs, OperatingPoint, PowerModel};
+use scheduling::{Resolution, Time, TimeUnit};
+use serde::Deserialize;
 use serde_json::json;
 
 #[derive(ValueEnum, Debug, Clone)]
@@ -14,6 +29,73 @@ enum DispatchAlgorithm {
     FirstFit,
     BestFit,
     WorstFit,
+    /// no partitioning, global EDF with migration across all CPUs.
+    /// jobs are aborted at their deadlines and the options of the
+    /// per-CPU simulation are rejected
+    Global,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "kebab_case")]
+enum SchedulingPolicy {
+    Edf,
+    RateMonotonic,
+    DeadlineMonotonic,
+    /// priorities are assigned per CPU with Audsley's algorithm
+    FixedPriority,
+    Fifo,
+    /// least laxity first
+    Llf,
+    /// modified least laxity first
+    ModifiedLlf,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "kebab_case")]
+enum TieBreak {
+    EarliestDeadline,
+    EarliestArrival,
+    LowestId,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "kebab_case")]
+enum DeadlineMiss {
+    AbortAtDeadline,
+    /// abort a job as soon as it can't meet its deadline anymore
+    AbortPredictively,
+    ContinueLate,
+    /// continue late and skip the next job of the task
+    SkipNext,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "kebab_case")]
+enum Governor {
+    /// always the highest frequency
+    Maximum,
+    /// the lowest frequency that keeps the CPU schedulable under EDF
+    StaticSlowdown,
+    /// cycle-conserving EDF
+    CycleConserving,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "kebab_case")]
+enum ReportUnit {
+    Ns,
+    Us,
+    Ms,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "kebab_case")]
+enum ServerKind {
+    /// constant bandwidth server, for EDF
+    Cbs,
+    Polling,
+    Deferrable,
+    Sporadic,
 }
 
 #[derive(Parser)]
@@ -24,6 +106,82 @@ struct Cli {
     #[arg(short, long, value_enum,default_value_t=DispatchAlgorithm::FirstFit)]
     dispatch_algorithm: DispatchAlgorithm,
 
+    /// which policy each CPU schedules its jobs with.
+    /// the tasks are partitioned with a schedulability test of the policy
+    #[arg(short, long, value_enum, default_value_t = SchedulingPolicy::Edf)]
+    scheduling_policy: SchedulingPolicy,
+
+    /// how often the laxities are reconsidered under (modified) LLF
+    #[arg(long, default_value_t = 1, value_parser = parse_positive)]
+    quantum: usize,
+
+    /// how (modified) LLF orders jobs of equal laxity
+    #[arg(long, value_enum, default_value_t = TieBreak::EarliestDeadline)]
+    tie_break: TieBreak,
+
+    /// what happens to the jobs that miss their deadlines
+    #[arg(long, value_enum, default_value_t = DeadlineMiss::AbortPredictively)]
+    deadline_miss: DeadlineMiss,
+
+    /// length of the simulation of every CPU in ticks,
+    /// defaults to the feasibility interval of the tasks of the CPU.
+    /// jobs still unfinished by then are reported as such
+    #[arg(long)]
+    horizon: Option<usize>,
+
+    /// real length of a tick, e.g. `250us` or `1ms`.
+    /// the reports of the CPUs then state every time in the unit of the tick,
+    /// or in `--report-unit`
+    #[arg(long, value_parser = parse_time)]
+    tick: Option<Time>,
+
+    /// unit of the times in the reports of the CPUs.
+    /// a tick has to be a whole number of it
+    #[arg(long, value_enum, requires = "tick")]
+    report_unit: Option<ReportUnit>,
+
+    /// only reports the tasks matching all of the comma separated
+    /// `name=<name>`, `label=<label>` and `<key>=<value>` attributes,
+    /// e.g. `label=control,subsystem=chassis`
+    #[arg(long, value_parser = parse_selector)]
+    select: Option<Selector>,
+
+    /// period of the scheduler timer in ticks. jobs are released and preempted
+    /// only at its interrupts, as on a tick-based kernel, and the reports show
+    /// how long every job waited for its release. scheduling is event-driven by default
+    #[arg(long, value_parser = parse_positive)]
+    timer_tick: Option<usize>,
+
+    /// cost of a context switch in ticks, charged on every CPU
+    #[arg(long, default_value_t = 0)]
+    context_switch_overhead: usize,
+
+    /// cost of releasing a job in ticks
+    #[arg(long, default_value_t = 0)]
+    release_overhead: usize,
+
+    /// cost of removing a completed or aborted job in ticks
+    #[arg(long, default_value_t = 0)]
+    completion_overhead: usize,
+
+    /// scales the frequency of every CPU with this governor and reports the energy,
+    /// the execution times are taken at the highest frequency
+    #[arg(long, value_enum)]
+    governor: Option<Governor>,
+
+    /// `frequency:voltage` operating points of the CPUs, in MHz and volts
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_parser = parse_operating_point,
+        default_value = "250:0.8,500:1.0,750:1.1,1000:1.2"
+    )]
+    operating_points: Vec<OperatingPoint>,
+
+    /// power drawn by a CPU regardless of its frequency, even when idle
+    #[arg(long, default_value_t = 0.0)]
+    static_power: f64,
+
     /// number of tasks
     #[arg(short, long)]
     num_tasks: usize,
@@ -32,6 +190,16 @@ struct Cli {
     #[arg(short = 'c', long)]
     num_cpu: usize,
 
+    /// speed of every CPU relative to the one the wcets are generated for,
+    /// e.g. `1,1,0.5,0.5` for a big.LITTLE board. all CPUs have a speed of 1 by default
+    #[arg(long, value_delimiter = ',')]
+    cpu_speeds: Option<Vec<f64>>,
+
+    /// path to a json object mapping task ids to their wcet on every CPU,
+    /// overriding the speeds of the CPUs for these tasks
+    #[arg(long)]
+    wcet_table: Option<PathBuf>,
+
     /// replication factor.
     /// replicaton factor of 0 means there is only 1 instance of each task
     #[arg(short, long)]
@@ -41,16 +209,189 @@ struct Cli {
     #[arg(short, long)]
     utilization: f32,
 
-    /// path to output file
+    /// path to output file. it holds the reports of the CPUs, in an object
+    /// under `cpus` along the `energy`, `dags` and `splits` of the whole system if any
     #[arg(short, long)]
     output_path: PathBuf,
+
+    /// path to a json file of aperiodic jobs to inject.
+    /// each entry has an `id`, `arrival-time`, `execution`,
+    /// an optional soft `deadline` and the `cpu` serving it (defaults to 0).
+    /// they all go to the server instead when there is one.
+    /// ids must differ from the ones of the tasks and of the other jobs
+    #[arg(long)]
+    aperiodic_jobs: Option<PathBuf>,
+
+    /// budget of a server serving all the aperiodic jobs,
+    /// placed like a task of utilization budget / period
+    #[arg(long, requires = "server_period")]
+    server_budget: Option<usize>,
+
+    /// period of the server
+    #[arg(long, requires = "server_budget")]
+    server_period: Option<usize>,
+
+    /// how the server replenishes its budget.
+    /// under fixed priorities it gets its priority like the tasks
+    #[arg(long, value_enum, default_value_t = ServerKind::Cbs)]
+    server_kind: ServerKind,
+
+    /// path to a json file of dag tasks to add to the generated ones.
+    /// each entry has an `id`, a `period`, an optional end-to-end `deadline` and `offset`,
+    /// the `[id, wcet]` `nodes` of its subtasks and the `[predecessor, successor]` `edges`.
+    /// subtask ids must differ from the ones of the generated tasks and the server,
+    /// which take the ids from 0 to `num-tasks`.
+    /// all the CPUs are then simulated together over the same horizon
+    #[arg(long)]
+    dags: Option<PathBuf>,
+}
+
+/// the options of the per-CPU simulation, which the global dispatcher doesn't support
+const PER_CPU_OPTIONS: [&str; 18] = [
+    "quantum",
+    "tie_break",
+    "horizon",
+    "tick",
+    "report_unit",
+    "select",
+    "timer_tick",
+    "context_switch_overhead",
+    "release_overhead",
+    "completion_overhead",
+    "governor",
+    "operating_points",
+    "static_power",
+    "aperiodic_jobs",
+    "server_budget",
+    "server_period",
+    "server_kind",
+    "dags",
+];
+
+impl Cli {
+    /// parses the command line, rejecting the options the dispatcher can't honour
+    fn parse_supported() -> Self {
+        let matches = Self::command().get_matches();
+        let cli = Self::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+        if let Some((tick, unit)) = cli.report_unit() {
+            if let Err(e) = Resolution::new(tick).per_tick(unit) {
+                Self::command().error(ErrorKind::ValueValidation, e).exit();
+            }
+        }
+        if !matches!(cli.dispatch_algorithm, DispatchAlgorithm::Global) {
+            return cli;
+        }
+        let given = |id: &str| {
+            matches
+                .value_source(id)
+                .is_some_and(|source| source != ValueSource::DefaultValue)
+        };
+        let unsupported = PER_CPU_OPTIONS
+            .into_iter()
+            .find(|id| given(id))
+            .or_else(|| {
+                (!matches!(cli.scheduling_policy, SchedulingPolicy::Edf))
+                    .then_some("scheduling_policy")
+            })
+            .or_else(|| {
+                (!matches!(cli.deadline_miss, DeadlineMiss::AbortAtDeadline)
+                    && given("deadline_miss"))
+                .then_some("deadline_miss")
+            });
+        if let Some(id) = unsupported {
+            let message = format!(
+                "--{} isn't supported by the global dispatcher",
+                id.replace('_', "-")
+            );
+            Self::command()
+                .error(ErrorKind::ArgumentConflict, message)
+                .exit();
+        }
+        cli
+    }
+
+    /// the length of a tick and the unit the reports state every time in
+    fn report_unit(&self) -> Option<(Time, TimeUnit)> {
+        let tick = self.tick?;
+        let unit = match self.report_unit {
+            Some(ReportUnit::Ns) => TimeUnit::Ns,
+            Some(ReportUnit::Us) => TimeUnit::Us,
+            Some(ReportUnit::Ms) => TimeUnit::Ms,
+            None => tick.unit(),
+        };
+        Some((tick, unit))
+    }
+}
+
+fn parse_positive(value: &str) -> Result<usize, String> {
+    match value.parse().map_err(|e| format!("{e}"))? {
+        0 => Err("has to be positive".into()),
+        value => Ok(value),
+    }
+}
+
+fn parse_operating_point(point: &str) -> Result<OperatingPoint, String> {
+    let (frequency, voltage) = point.split_once(':').ok_or("expected frequency:voltage")?;
+    let frequency: usize = frequency.parse().map_err(|e| format!("{e}"))?;
+    let voltage: f64 = voltage.parse().map_err(|e| format!("{e}"))?;
+    if frequency == 0 {
+        return Err("frequency has to be positive".into());
+    }
+    Ok(OperatingPoint::new(frequency, voltage))
+}
+
+fn parse_time(time: &str) -> Result<Time, String> {
+    let (value, unit): (&str, fn(u64) -> Time) = match time {
+        t if t.ends_with("ns") => (&t[..t.len() - 2], Time::ns),
+        t if t.ends_with("us") => (&t[..t.len() - 2], Time::us),
+        t if t.ends_with("ms") => (&t[..t.len() - 2], Time::ms),
+        _ => return Err("expected a time in ns, us or ms".into()),
+    };
+    let value: u64 = value.parse().map_err(|e| format!("{e}"))?;
+    if value == 0 {
+        return Err("time has to be positive".into());
+    }
+    Ok(unit(value))
+}
+
+fn parse_selector(selector: &str) -> Result<Selector, String> {
+    let mut parsed = Selector::new();
+    for item in selector.split(',') {
+        parsed = match item.split_once('=').ok_or("expected key=value")? {
+            ("name", name) => parsed.with_name(name),
+            ("label", label) => parsed.with_label(label),
+            (key, value) => parsed.with_attribute(key, value),
+        };
+    }
+    Ok(parsed)
+}
+
+#[derive(Deserialize)]
+struct DagEntry {
+    id: usize,
+    period: usize,
+    deadline: Option<usize>,
+    #[serde(default)]
+    offset: usize,
+    nodes: Vec<(usize, usize)>,
+    #[serde(default)]
+    edges: Vec<(usize, usize)>,
+}
+
+#[derive(Deserialize)]
+struct AperiodicEntry {
+    #[serde(default)]
+    cpu: usize,
+    #[serde(flatten)]
+    job: AperiodicJob,
 }
 
 fn main() -> std::io::Result<()> {
-    let periods = vec![100, 200, 300, 400, 500, 600];
+    let periods = [100, 200, 300, 400, 500, 600];
     let mut rng = rand::thread_rng();
 
-    let cli = Cli::parse();
+    let cli = Cli::parse_supported();
+    let report_unit = cli.report_unit();
 
     let mut tasks = Vec::with_capacity(cli.num_tasks);
     for (id, utilization) in uunifast(cli.num_tasks, cli.utilization).iter().enumerate() {
@@ -58,25 +399,260 @@ fn main() -> std::io::Result<()> {
         let wcet = ((*period as f32) * utilization) as usize;
         tasks.push(Task::new(id, wcet, *period))
     }
-    let tasklist = TaskList::from(tasks).with_replication(cli.replication_factor);
+    let server_kind = match cli.server_kind {
+        ServerKind::Cbs => scheduling::ServerKind::Cbs,
+        ServerKind::Polling => scheduling::ServerKind::Polling,
+        ServerKind::Deferrable => scheduling::ServerKind::Deferrable,
+        ServerKind::Sporadic => scheduling::ServerKind::Sporadic,
+    };
+    // the server takes the id after the last task
+    let server = cli
+        .server_budget
+        .zip(cli.server_period)
+        .map(|(budget, period)| {
+            tasks.push(Task::server(cli.num_tasks, server_kind, budget, period));
+            cli.num_tasks
+        });
+    let dag_entries: Vec<DagEntry> = match cli.dags {
+        Some(path) => serde_json::from_slice(&std::fs::read(path)?)?,
+        None => Vec::new(),
+    };
+    let mut dags = Vec::with_capacity(dag_entries.len());
+    for entry in dag_entries {
+        let dag = DagTask::new(entry.id, entry.period, entry.nodes, entry.edges)
+            .unwrap_or_else(|e| panic!("invalid dag {}: {e:?}", entry.id));
+        let dag = match entry.deadline {
+            Some(deadline) => dag.with_deadline(deadline),
+            None => dag,
+        };
+        let dag = dag.with_offset(entry.offset);
+        for task in dag.tasks() {
+            if tasks.iter().any(|t| t.id() == task.id()) {
+                panic!("dag {} reuses the task id {}", entry.id, task.id());
+            }
+            tasks.push(task);
+        }
+        dags.push(dag);
+    }
+    let mut platform = match cli.cpu_speeds {
+        Some(speeds) if speeds.len() != cli.num_cpu => {
+            panic!("got {} CPU speeds for {} CPUs", speeds.len(), cli.num_cpu)
+        }
+        Some(speeds) => Platform::uniform(speeds),
+        None => Platform::identical(cli.num_cpu),
+    };
+    if let Some(path) = cli.wcet_table {
+        let table: HashMap<usize, Vec<usize>> = serde_json::from_slice(&std::fs::read(path)?)?;
+        for (id, wcets) in table {
+            let task = tasks
+                .iter()
+                .find(|t| t.id() == id)
+                .unwrap_or_else(|| panic!("no task with id {id} in the wcet table"));
+            platform = platform.with_wcets(task, wcets);
+        }
+    }
+    let aperiodic_jobs: Vec<AperiodicEntry> = match cli.aperiodic_jobs {
+        Some(path) => serde_json::from_slice(&std::fs::read(path)?)?,
+        None => Vec::new(),
+    };
+    // aperiodic jobs are reported along the jobs of the tasks, so they need ids of their own
+    let mut ids: HashSet<usize> = tasks.iter().map(|t| t.id()).collect();
+    for entry in &aperiodic_jobs {
+        let message = if entry.cpu >= cli.num_cpu {
+            format!(
+                "aperiodic job {} goes to CPU {} but there are {} CPUs",
+                entry.job.id(),
+                entry.cpu,
+                cli.num_cpu
+            )
+        } else if !ids.insert(entry.job.id()) {
+            format!(
+                "aperiodic job {} reuses the id of a task or another job",
+                entry.job.id()
+            )
+        } else {
+            continue;
+        };
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            message,
+        ));
+    }
+    // every CPU only takes the tasks the policy it schedules them with can handle
+    let admission = match cli.scheduling_policy {
+        SchedulingPolicy::Edf | SchedulingPolicy::Llf | SchedulingPolicy::ModifiedLlf => {
+            Admission::Edf
+        }
+        SchedulingPolicy::RateMonotonic => Admission::RateMonotonic,
+        SchedulingPolicy::DeadlineMonotonic => Admission::DeadlineMonotonic,
+        SchedulingPolicy::FixedPriority => Admission::FixedPriority,
+        SchedulingPolicy::Fifo => Admission::Fifo,
+    };
+    let tasklist = TaskList::from(tasks)
+        .with_replication(cli.replication_factor)
+        .with_admission(admission);
     let dispatched_list = match cli.dispatch_algorithm {
-        DispatchAlgorithm::FirstFit => tasklist.first_fit(cli.num_cpu),
-        DispatchAlgorithm::BestFit => tasklist.best_fit(cli.num_cpu),
-        DispatchAlgorithm::WorstFit => tasklist.worst_fit(cli.num_cpu),
+        DispatchAlgorithm::FirstFit => tasklist.first_fit_on(&platform),
+        DispatchAlgorithm::BestFit => tasklist.best_fit_on(&platform),
+        DispatchAlgorithm::WorstFit => tasklist.worst_fit_on(&platform),
+        DispatchAlgorithm::Global => {
+            let report = schedule_global_on(&tasklist, &platform);
+            let json_string = serde_json::to_string_pretty(&report).unwrap();
+            return std::fs::write(cli.output_path, json_string);
+        }
+    };
+    // split the tasks that don't fit anywhere as a whole, the pieces are placed for EDF
+    let semi_partitioned =
+        dispatched_list.is_err() && matches!(cli.scheduling_policy, SchedulingPolicy::Edf);
+    let dispatched_list = match semi_partitioned {
+        true => tasklist.semi_partitioned_on(&platform),
+        false => dispatched_list,
     };
     let dispatched_list = match dispatched_list {
         Ok(tasks) => tasks,
-        Err(_) => panic!("couldn't dispatch jobs into CPUs"),
+        Err(_) => {
+            let verdict = match schedule_global_on(&tasklist, &platform).meets_all_deadlines() {
+                true => "would meet all deadlines",
+                false => "would miss deadlines too",
+            };
+            panic!("couldn't dispatch jobs into CPUs, global EDF {verdict}")
+        }
     };
+    let tie_break = match cli.tie_break {
+        TieBreak::EarliestDeadline => scheduling::TieBreak::EarliestDeadline,
+        TieBreak::EarliestArrival => scheduling::TieBreak::EarliestArrival,
+        TieBreak::LowestId => scheduling::TieBreak::LowestId,
+    };
+    let deadline_miss = match cli.deadline_miss {
+        DeadlineMiss::AbortAtDeadline => scheduling::DeadlineMiss::AbortAtDeadline,
+        DeadlineMiss::AbortPredictively => scheduling::DeadlineMiss::AbortPredictively,
+        DeadlineMiss::ContinueLate => scheduling::DeadlineMiss::ContinueLate,
+        DeadlineMiss::SkipNext => scheduling::DeadlineMiss::SkipNext,
+    };
+    let dvfs = cli.governor.map(|governor| {
+        let governor = match governor {
+            Governor::Maximum => scheduling::Governor::Maximum,
+            Governor::StaticSlowdown => scheduling::Governor::StaticSlowdown,
+            Governor::CycleConserving => scheduling::Governor::CycleConserving,
+        };
+        Dvfs::new(cli.operating_points.clone())
+            .with_power_model(PowerModel::new(1.0).with_static_power(cli.static_power))
+            .with_governor(governor)
+    });
+    let overheads = Overheads::new()
+        .with_context_switch(cli.context_switch_overhead)
+        .with_release(cli.release_overhead)
+        .with_completion(cli.completion_overhead);
+    // dags and split tasks span CPUs, which then run together
+    // over the feasibility interval of the whole system
+    let together = !dags.is_empty() || semi_partitioned;
+    let system_horizon = cli
+        .horizon
+        .unwrap_or_else(|| tasklist.feasibility_interval());
+    let mut joblists = Vec::new();
+    let mut horizons = Vec::new();
+    // under fixed priorities the tasks of every CPU come with their priorities
+    for (i, tasklist) in dispatched_list.into_iter().enumerate() {
+        let horizon = match together {
+            false => cli
+                .horizon
+                .unwrap_or_else(|| tasklist.feasibility_interval()),
+            true => system_horizon,
+        };
+        let joblist = tasklist
+            .jobs_till(horizon)
+            .with_deadline_miss(deadline_miss)
+            .with_overheads(overheads)
+            .with_platform(platform.clone(), i);
+        let joblist = match &dvfs {
+            Some(dvfs) => joblist.with_dvfs(dvfs.clone()),
+            None => joblist,
+        };
+        let joblist = match cli.timer_tick {
+            Some(tick) => joblist.with_timer_tick(tick),
+            None => joblist,
+        };
+        let mut joblist = match cli.scheduling_policy {
+            SchedulingPolicy::Edf => joblist.with_policy(Edf),
+            SchedulingPolicy::RateMonotonic => joblist.with_policy(RateMonotonic),
+            SchedulingPolicy::DeadlineMonotonic => joblist.with_policy(DeadlineMonotonic),
+            SchedulingPolicy::FixedPriority => joblist.with_policy(FixedPriority),
+            SchedulingPolicy::Fifo => joblist.with_policy(Fifo),
+            SchedulingPolicy::Llf => joblist.with_policy(
+                Llf::new()
+                    .with_quantum(cli.quantum)
+                    .with_tie_break(tie_break),
+            ),
+            SchedulingPolicy::ModifiedLlf => joblist.with_policy(
+                Llf::modified()
+                    .with_quantum(cli.quantum)
+                    .with_tie_break(tie_break),
+            ),
+        };
+        match server {
+            // every replica of the server serves all the aperiodic jobs
+            Some(server) if tasklist.contains(server) => {
+                for entry in aperiodic_jobs.iter() {
+                    joblist.push_aperiodic(entry.job.clone().with_server(server));
+                }
+            }
+            Some(_) => {}
+            None => {
+                for entry in aperiodic_jobs.iter().filter(|e| e.cpu == i) {
+                    joblist.push_aperiodic(entry.job.clone());
+                }
+            }
+        }
+        joblists.push(joblist);
+        horizons.push(horizon);
+    }
+    let dag_reports = match together {
+        false => {
+            for (joblist, horizon) in joblists.iter_mut().zip(&horizons) {
+                joblist.schedule_until(*horizon);
+            }
+            Vec::new()
+        }
+        true => schedule_dags(&mut joblists, &dags, system_horizon),
+    };
+    let split_reports = split_reports(&joblists);
     let mut reports = Vec::new();
-    for (i, tasklist) in dispatched_list.iter().enumerate() {
-        let mut joblist = tasklist.jobs_till_hyperperiod();
-        joblist.schedule();
+    let mut energy = 0.0;
+    for (i, (joblist, horizon)) in joblists.iter().zip(horizons).enumerate() {
+        let report = match report_unit {
+            Some((tick, unit)) => joblist
+                .report(horizon)
+                .in_unit(&Resolution::new(tick), unit)
+                .map_err(std::io::Error::other)?,
+            None => joblist.report(horizon),
+        };
+        let report = match &cli.select {
+            Some(selector) => report.filter(selector),
+            None => report,
+        };
+        energy += report.energy().unwrap_or(0.0);
         reports.push(json!({
             "cpu": i,
-            "report": joblist.report(tasklist.hyperperiod()),
+            "report": report,
         }));
     }
-    let json_string = serde_json::to_string_pretty(&reports).unwrap();
+    // the reports of the whole system go along the ones of the CPUs
+    let output = match dvfs.is_some() || !dags.is_empty() || !split_reports.is_empty() {
+        false => json!(reports),
+        true => {
+            let mut output = json!({ "cpus": reports });
+            if dvfs.is_some() {
+                output["energy"] = json!(energy);
+            }
+            if !dags.is_empty() {
+                output["dags"] = json!(dag_reports);
+            }
+            if !split_reports.is_empty() {
+                output["splits"] = json!(split_reports);
+            }
+            output
+        }
+    };
+    let json_string = serde_json::to_string_pretty(&output).unwrap();
     std::fs::write(cli.output_path, json_string)
 }
@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+/// human readable description of a task, copied into the reports of its jobs
+#[derive(Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct Metadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) name: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) labels: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) attributes: BTreeMap<String, String>,
+}
+
+/// selects jobs by the metadata of their task.
+/// a job matches when it has every label and attribute of the selector
+#[derive(Clone, Debug, Default)]
+pub struct Selector {
+    name: Option<String>,
+    labels: Vec<String>,
+    attributes: Vec<(String, String)>,
+}
+
+impl Selector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_name(self, name: impl Into<String>) -> Self {
+        Self {
+            name: Some(name.into()),
+            ..self
+        }
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.labels.push(label.into());
+        self
+    }
+
+    pub fn with_attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.attributes.push((key.into(), value.into()));
+        self
+    }
+
+    pub(crate) fn matches(&self, metadata: &Metadata) -> bool {
+        self.name
+            .as_ref()
+            .is_none_or(|name| metadata.name.as_ref() == Some(name))
+            && self.labels.iter().all(|l| metadata.labels.contains(l))
+            && self
+                .attributes
+                .iter()
+                .all(|(k, v)| metadata.attributes.get(k) == Some(v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches() {
+        let metadata = Metadata {
+            name: Some("brake".to_string()),
+            labels: vec!["control".to_string(), "dal-a".to_string()],
+            attributes: BTreeMap::from([("subsystem".to_string(), "chassis".to_string())]),
+        };
+        assert!(Selector::new().matches(&metadata));
+        assert!(Selector::new().with_name("brake").matches(&metadata));
+        assert!(!Selector::new().with_name("steer").matches(&metadata));
+        assert!(Selector::new()
+            .with_label("dal-a")
+            .with_attribute("subsystem", "chassis")
+            .matches(&metadata));
+        assert!(!Selector::new().with_label("logging").matches(&metadata));
+        assert!(!Selector::new()
+            .with_attribute("subsystem", "powertrain")
+            .matches(&metadata));
+    }
+}
@@ -0,0 +1,59 @@
+use serde::Serialize;
+
+/// time a processor spends on the scheduler itself instead of the jobs,
+/// all zero by default
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Overheads {
+    context_switch: usize,
+    release: usize,
+    completion: usize,
+}
+
+impl Overheads {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// cost of starting or resuming a job other than the one that ran last
+    pub fn with_context_switch(self, context_switch: usize) -> Self {
+        Self {
+            context_switch,
+            ..self
+        }
+    }
+
+    /// cost of releasing a job, e.g. the timer interrupt handling its arrival
+    pub fn with_release(self, release: usize) -> Self {
+        Self { release, ..self }
+    }
+
+    /// cost of removing a completed or aborted job
+    pub fn with_completion(self, completion: usize) -> Self {
+        Self { completion, ..self }
+    }
+
+    pub(crate) fn cost(&self, kind: OverheadKind) -> usize {
+        match kind {
+            OverheadKind::ContextSwitch => self.context_switch,
+            OverheadKind::Release => self.release,
+            OverheadKind::Completion => self.completion,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum OverheadKind {
+    ContextSwitch,
+    Release,
+    Completion,
+}
+
+/// the processor was busy with an overhead of `kind` during `from..to`
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct Overhead {
+    pub(crate) kind: OverheadKind,
+    pub(crate) from: usize,
+    pub(crate) to: usize,
+}
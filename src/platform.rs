@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use crate::task::Task;
+
+/// rate at which a processor executes jobs, `cycles` per tick.
+/// execution times are in ticks of a processor doing `unit` cycles per tick
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Speed {
+    pub(crate) cycles: usize,
+    pub(crate) unit: usize,
+}
+
+impl Speed {
+    pub(crate) const FULL: Speed = Speed { cycles: 1, unit: 1 };
+
+    /// a processor running at `self` of a processor running at `other`
+    pub(crate) fn times(self, other: Speed) -> Speed {
+        Speed {
+            cycles: self.cycles * other.cycles,
+            unit: self.unit * other.unit,
+        }
+    }
+
+    /// time it takes to execute for `execution` ticks of the reference processor
+    pub(crate) fn time(self, execution: usize) -> usize {
+        (execution * self.unit).div_ceil(self.cycles)
+    }
+}
+
+/// speed factors are kept to this precision
+const SPEED_UNIT: usize = 1000;
+
+/// the processors the tasks are placed on.
+/// the wcets of the tasks are given for a reference processor of speed 1
+#[derive(Clone, Debug, PartialEq)]
+pub struct Platform {
+    /// speed of every processor relative to the reference one
+    speeds: Vec<Speed>,
+    /// speed of the processors for the tasks with a row in the wcet table
+    unrelated: HashMap<usize, Vec<Speed>>,
+}
+
+impl Platform {
+    /// `num_proc` processors of speed 1
+    pub fn identical(num_proc: usize) -> Self {
+        Self::uniform(vec![1.0; num_proc])
+    }
+
+    /// processors that execute every task `speeds[i]` times as fast as
+    /// the reference processor, e.g. 0.5 for a little core
+    pub fn uniform(speeds: Vec<f64>) -> Self {
+        let speeds = speeds
+            .into_iter()
+            .map(|speed| {
+                let cycles = (speed * SPEED_UNIT as f64).round() as usize;
+                assert!(cycles > 0, "speed has to be positive");
+                Speed {
+                    cycles,
+                    unit: SPEED_UNIT,
+                }
+            })
+            .collect();
+        Self {
+            speeds,
+            unrelated: HashMap::new(),
+        }
+    }
+
+    /// sets the wcet of `task` on every processor, as a row of an
+    /// unrelated-machines wcet table. it overrides the speeds of the processors
+    /// for the task, its other execution times scale along with its wcet
+    pub fn with_wcets(mut self, task: &Task, wcets: Vec<usize>) -> Self {
+        assert_eq!(wcets.len(), self.num_proc(), "one wcet per processor");
+        assert!(task.wcet() > 0, "task has no execution time to scale");
+        let speeds = wcets
+            .into_iter()
+            .map(|wcet| {
+                assert!(wcet > 0, "wcet has to be positive");
+                Speed {
+                    cycles: task.wcet(),
+                    unit: wcet,
+                }
+            })
+            .collect();
+        self.unrelated.insert(task.id(), speeds);
+        self
+    }
+
+    pub fn num_proc(&self) -> usize {
+        self.speeds.len()
+    }
+
+    /// whether all the processors run every task at the same speed
+    pub(crate) fn is_identical(&self) -> bool {
+        self.unrelated.is_empty() && self.speeds.windows(2).all(|s| s[0] == s[1])
+    }
+
+    /// speed of processor `index` for the jobs of task `id`
+    pub(crate) fn speed(&self, id: usize, index: usize) -> Speed {
+        self.unrelated
+            .get(&id)
+            .map_or(self.speeds[index], |speeds| speeds[index])
+    }
+
+    /// speed of processor `index` for the jobs that aren't of any task
+    pub(crate) fn processor_speed(&self, index: usize) -> Speed {
+        self.speeds[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaled_wcets() {
+        let task = Task::new(2, 3, 10);
+        let platform = Platform::uniform(vec![1.0, 0.5]).with_wcets(&task, vec![2, 9]);
+        assert_eq!(platform.speed(1, 0).time(4), 4);
+        assert_eq!(platform.speed(1, 1).time(4), 8);
+        assert_eq!(platform.speed(2, 0).time(3), 2);
+        assert_eq!(platform.speed(2, 1).time(3), 9);
+        // proportional for parts of the task
+        assert_eq!(platform.speed(2, 1).time(1), 3);
+        assert_eq!(platform.processor_speed(1).time(3), 6);
+    }
+}
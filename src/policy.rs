@@ -0,0 +1,168 @@
+use crate::job::Job;
+
+/// decides which of the ready jobs of a processor runs next
+pub trait SchedulingPolicy {
+    /// priority of `job` at time `now`, the ready job with the smallest value runs.
+    /// jobs of equal priority and tie break swap places at every decision,
+    /// so they take turns on the processor
+    fn priority(&self, job: &Job, now: usize) -> usize;
+
+    /// breaks ties between jobs of equal priority, the smallest value runs first
+    fn tie_break(&self, _job: &Job) -> usize {
+        0
+    }
+
+    /// longest interval a job runs for before the policy is asked again.
+    /// `None` only reconsiders at arrivals, completions and preemption points
+    fn quantum(&self) -> Option<usize> {
+        None
+    }
+
+    /// whether `running`, the job that ran last, keeps the processor
+    /// although `candidate` has a higher priority at `now`
+    fn keep_running(&self, _running: &Job, _candidate: &Job, _now: usize) -> bool {
+        false
+    }
+
+    /// whether the priority of a waiting job changes with time,
+    /// in which case all the ready jobs are sorted again at every decision
+    fn is_time_dependent(&self) -> bool {
+        false
+    }
+}
+
+/// Earliest Deadline First
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Edf;
+
+impl SchedulingPolicy for Edf {
+    fn priority(&self, job: &Job, _now: usize) -> usize {
+        job.scheduling_deadline()
+    }
+}
+
+/// Rate Monotonic, the shorter the period the higher the priority
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RateMonotonic;
+
+impl SchedulingPolicy for RateMonotonic {
+    fn priority(&self, job: &Job, _now: usize) -> usize {
+        job.period()
+    }
+}
+
+/// Deadline Monotonic, the shorter the relative deadline the higher the priority
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DeadlineMonotonic;
+
+impl SchedulingPolicy for DeadlineMonotonic {
+    fn priority(&self, job: &Job, _now: usize) -> usize {
+        job.relative_deadline()
+    }
+}
+
+/// preemptive fixed-priority scheduling with the priorities of the tasks,
+/// see `Task::with_priority` and `TaskList::assign_priorities`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FixedPriority;
+
+impl SchedulingPolicy for FixedPriority {
+    fn priority(&self, job: &Job, _now: usize) -> usize {
+        job.priority()
+    }
+}
+
+/// First In First Out, jobs run in the order they arrived in
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Fifo;
+
+impl SchedulingPolicy for Fifo {
+    fn priority(&self, job: &Job, _now: usize) -> usize {
+        job.arrival_time()
+    }
+}
+
+/// how Least Laxity First orders jobs of equal laxity
+#[derive(Clone, Copy, Debug, Default)]
+pub enum TieBreak {
+    #[default]
+    EarliestDeadline,
+    EarliestArrival,
+    LowestId,
+}
+
+/// Least Laxity First, the job with the least slack (deadline - now - remaining wcet)
+/// runs first. laxities are reconsidered every `quantum`, 1 by default
+#[derive(Clone, Copy, Debug)]
+pub struct Llf {
+    quantum: usize,
+    tie_break: TieBreak,
+    modified: bool,
+}
+
+impl Default for Llf {
+    fn default() -> Self {
+        Self {
+            quantum: 1,
+            tie_break: TieBreak::default(),
+            modified: false,
+        }
+    }
+}
+
+impl Llf {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Modified LLF: a running job is only preempted once
+    /// a waiting job has no laxity left, which avoids the thrashing
+    /// between jobs of equal laxity
+    pub fn modified() -> Self {
+        Self {
+            modified: true,
+            ..Self::default()
+        }
+    }
+
+    /// reconsiders the laxities every `quantum` units of execution
+    pub fn with_quantum(self, quantum: usize) -> Self {
+        assert!(quantum > 0, "empty scheduling quantum");
+        Self { quantum, ..self }
+    }
+
+    pub fn with_tie_break(self, tie_break: TieBreak) -> Self {
+        Self { tie_break, ..self }
+    }
+
+    fn laxity(job: &Job, now: usize) -> usize {
+        job.scheduling_deadline()
+            .saturating_sub(now + job.remaining_wcet())
+    }
+}
+
+impl SchedulingPolicy for Llf {
+    fn priority(&self, job: &Job, now: usize) -> usize {
+        Self::laxity(job, now)
+    }
+
+    fn tie_break(&self, job: &Job) -> usize {
+        match self.tie_break {
+            TieBreak::EarliestDeadline => job.scheduling_deadline(),
+            TieBreak::EarliestArrival => job.arrival_time(),
+            TieBreak::LowestId => job.id(),
+        }
+    }
+
+    fn quantum(&self) -> Option<usize> {
+        Some(self.quantum)
+    }
+
+    fn keep_running(&self, _running: &Job, candidate: &Job, now: usize) -> bool {
+        self.modified && Self::laxity(candidate, now) > 0
+    }
+
+    fn is_time_dependent(&self) -> bool {
+        true
+    }
+}
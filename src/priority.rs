@@ -0,0 +1,81 @@
+use crate::task::Task;
+
+/// schedulability test of a task under preemptive fixed-priority scheduling.
+/// to be used for Optimal Priority Assignment, the verdict must only depend
+/// on the set of the higher priority tasks, not on their relative order
+pub trait SchedulabilityTest {
+    /// whether `task` meets its deadline when `higher_priority` are the
+    /// tasks with a higher priority than it on the same processor
+    fn is_schedulable(&self, task: &Task, higher_priority: &[&Task]) -> bool;
+}
+
+/// response time analysis for preemptive fixed-priority scheduling.
+/// the worst-case response time R = C + sum(ceil(R / T_j) * C_j) over the
+/// higher priority tasks has to fit in min(deadline, period), which makes it
+/// exact for constrained deadlines and sufficient otherwise
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ResponseTimeAnalysis;
+
+impl ResponseTimeAnalysis {
+    /// worst-case response time of `task`, `None` if it exceeds `bound`
+    fn response_time(task: &Task, higher_priority: &[&Task], bound: usize) -> Option<usize> {
+        let mut response = task.wcet();
+        loop {
+            let next = task.wcet()
+                + higher_priority
+                    .iter()
+                    .map(|t| response.div_ceil(t.period()) * t.wcet())
+                    .sum::<usize>();
+            if next > bound {
+                return None;
+            } else if next == response {
+                return Some(response);
+            }
+            response = next;
+        }
+    }
+}
+
+impl SchedulabilityTest for ResponseTimeAnalysis {
+    fn is_schedulable(&self, task: &Task, higher_priority: &[&Task]) -> bool {
+        let bound = task.deadline().min(task.period());
+        Self::response_time(task, higher_priority, bound).is_some()
+    }
+}
+
+/// how the partitioners check that a processor can take one more task,
+/// which has to match the policy the processor schedules its jobs with
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Admission {
+    /// EDF density test with the blocking of non-preemptive regions
+    #[default]
+    Edf,
+    /// response time analysis, the shorter the period the higher the priority
+    RateMonotonic,
+    /// response time analysis, the shorter the relative deadline the higher the priority
+    DeadlineMonotonic,
+    /// response time analysis with the priorities of Audsley's OPA,
+    /// which the tasks of every processor are given
+    FixedPriority,
+    /// the longest busy period fits in the shortest deadline
+    Fifo,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn response_time() {
+        let t1 = Task::new(1, 1, 4);
+        let t2 = Task::new(2, 2, 6);
+        let t3 = Task::new(3, 3, 12);
+        assert_eq!(ResponseTimeAnalysis::response_time(&t1, &[], 4), Some(1));
+        assert_eq!(ResponseTimeAnalysis::response_time(&t2, &[&t1], 6), Some(3));
+        assert_eq!(
+            ResponseTimeAnalysis::response_time(&t3, &[&t1, &t2], 12),
+            Some(10)
+        );
+        assert!(!ResponseTimeAnalysis.is_schedulable(&t3.with_deadline(9), &[&t1, &t2]));
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::rc::Rc;
+
+use crate::job::Job;
+use crate::policy::SchedulingPolicy;
+
+/// (priority, tie break) of a job, the smallest runs first
+type Key = (usize, usize);
+
+/// the ready jobs of a processor, grouped by the key the policy gives them.
+/// jobs are queued unsorted and get sorted at the next decision, so that they
+/// are kept in the order of a list sorted by decreasing priority at every
+/// decision. that sort flips the order of the jobs of equal key each time
+pub(crate) struct ReadyQueue {
+    policy: Rc<dyn SchedulingPolicy>,
+    /// the sorted jobs of each key, in reverse order if `flipped`
+    groups: BTreeMap<Key, VecDeque<Job>>,
+    flipped: bool,
+    /// jobs queued since the last sort, in order
+    unsorted: Vec<Job>,
+    /// key of every sorted job, by (id, iteration)
+    keys: HashMap<(usize, usize), Key>,
+}
+
+impl ReadyQueue {
+    pub(crate) fn new(policy: Rc<dyn SchedulingPolicy>) -> Self {
+        Self {
+            policy,
+            groups: BTreeMap::new(),
+            flipped: false,
+            unsorted: Vec::new(),
+            keys: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.groups.is_empty() && self.unsorted.is_empty()
+    }
+
+    pub(crate) fn push(&mut self, job: Job) {
+        self.unsorted.push(job);
+    }
+
+    /// sorts the queued jobs by their priority at `now`.
+    /// the jobs already sorted keep their key unless the policy is time dependent
+    pub(crate) fn sort(&mut self, now: usize) {
+        let jobs = match self.policy.is_time_dependent() {
+            true => self.drain(),
+            false => std::mem::take(&mut self.unsorted),
+        };
+        for job in jobs {
+            let key = (self.policy.priority(&job, now), self.policy.tie_break(&job));
+            self.keys.insert((job.id(), job.iteration()), key);
+            let group = self.groups.entry(key).or_default();
+            match self.flipped {
+                false => group.push_back(job),
+                true => group.push_front(job),
+            }
+        }
+        self.flipped = !self.flipped;
+    }
+
+    /// removes all the jobs, in order
+    pub(crate) fn drain(&mut self) -> Vec<Job> {
+        let flipped = self.flipped;
+        let mut jobs: Vec<Job> = std::mem::take(&mut self.groups)
+            .into_values()
+            .rev()
+            .flat_map(|group| in_order(group.into_iter(), flipped))
+            .collect();
+        jobs.append(&mut self.unsorted);
+        self.keys.clear();
+        jobs
+    }
+
+    /// the jobs by decreasing priority as of the last sort,
+    /// followed by the ones queued since
+    pub(crate) fn iter(&self) -> impl DoubleEndedIterator<Item = &Job> {
+        self.groups
+            .values()
+            .rev()
+            .flat_map(|group| in_order(group.iter(), self.flipped))
+            .chain(self.unsorted.iter())
+    }
+
+    /// the sorted jobs with a priority higher than `priority`, by decreasing priority
+    pub(crate) fn above_mut(&mut self, priority: usize) -> impl Iterator<Item = &mut Job> {
+        let flipped = self.flipped;
+        self.groups
+            .range_mut(..(priority, 0))
+            .rev()
+            .flat_map(move |(_, group)| in_order(group.iter_mut(), flipped))
+    }
+
+    /// the job with (id, iteration) `key`
+    pub(crate) fn get(&self, key: (usize, usize)) -> Option<&Job> {
+        let is_key = |job: &&Job| (job.id(), job.iteration()) == key;
+        match self.keys.get(&key) {
+            Some(k) => self.groups[k].iter().find(is_key),
+            None => self.unsorted.iter().find(is_key),
+        }
+    }
+
+    pub(crate) fn get_mut(&mut self, key: (usize, usize)) -> Option<&mut Job> {
+        let is_key = |job: &&mut Job| (job.id(), job.iteration()) == key;
+        match self.keys.get(&key) {
+            Some(k) => self.groups.get_mut(k).unwrap().iter_mut().find(is_key),
+            None => self.unsorted.iter_mut().find(is_key),
+        }
+    }
+
+    pub(crate) fn remove(&mut self, key: (usize, usize)) -> Option<Job> {
+        let is_key = |job: &Job| (job.id(), job.iteration()) == key;
+        match self.keys.remove(&key) {
+            Some(k) => {
+                let group = self.groups.get_mut(&k).unwrap();
+                let job = group.remove(group.iter().position(is_key).unwrap());
+                if group.is_empty() {
+                    self.groups.remove(&k);
+                }
+                job
+            }
+            None => {
+                let index = self.unsorted.iter().position(is_key)?;
+                Some(self.unsorted.remove(index))
+            }
+        }
+    }
+}
+
+/// `iter` in reverse if `flipped`
+fn in_order<I: DoubleEndedIterator>(
+    iter: I,
+    flipped: bool,
+) -> impl DoubleEndedIterator<Item = I::Item> {
+    let (forward, backward) = match flipped {
+        false => (Some(iter), None),
+        true => (None, Some(iter.rev())),
+    };
+    forward
+        .into_iter()
+        .flatten()
+        .chain(backward.into_iter().flatten())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::Edf;
+
+    #[test]
+    fn ties_flip_at_every_sort() {
+        let mut queue = ReadyQueue::new(Rc::new(Edf));
+        queue.push(Job::new(1, 0, 0, 1, 10));
+        queue.push(Job::new(2, 0, 0, 1, 5));
+        queue.push(Job::new(3, 0, 0, 1, 10));
+        let ids = |queue: &ReadyQueue| queue.iter().map(Job::id).collect::<Vec<_>>();
+        assert_eq!(ids(&queue), vec![1, 2, 3]);
+
+        // by decreasing priority, the highest priority last
+        queue.sort(0);
+        assert_eq!(ids(&queue), vec![3, 1, 2]);
+        queue.sort(0);
+        assert_eq!(ids(&queue), vec![1, 3, 2]);
+
+        queue.push(Job::new(4, 0, 0, 1, 10));
+        assert_eq!(ids(&queue), vec![1, 3, 2, 4]);
+        queue.sort(0);
+        assert_eq!(ids(&queue), vec![4, 3, 1, 2]);
+
+        assert_eq!(queue.remove((3, 0)).map(|j| j.id()), Some(3));
+        assert!(queue.get((3, 0)).is_none());
+        assert_eq!(ids(&queue), vec![4, 1, 2]);
+        assert_eq!(
+            queue.above_mut(10).map(|j| j.id()).collect::<Vec<_>>(),
+            vec![2]
+        );
+    }
+}
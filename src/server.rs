@@ -0,0 +1,315 @@
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use serde::Serialize;
+
+use crate::job::Job;
+use crate::metadata::Metadata;
+
+/// how a server replenishes its budget
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ServerKind {
+    /// Constant Bandwidth Server, for EDF.
+    /// an exhausted budget is recharged right away and the server deadline
+    /// is postponed by a period, so the server never takes more than
+    /// budget / period of the processor
+    Cbs,
+    /// the budget is recharged at the start of every period, but is lost
+    /// as soon as there is no request to serve
+    Polling,
+    /// the budget is recharged at the start of every period and is kept
+    /// until the end of it, even while there is no request to serve
+    Deferrable,
+    /// the budget consumed since the server became active is given back
+    /// one period after that activation
+    Sporadic,
+}
+
+/// a reservation serving aperiodic requests in FIFO order
+pub(crate) struct Server {
+    pub(crate) id: usize,
+    kind: ServerKind,
+    budget: usize,
+    period: usize,
+    /// fixed priority, 0 being the highest
+    priority: usize,
+    /// budget left
+    capacity: usize,
+    deadline: usize,
+    /// start of the next period of polling and deferrable servers
+    next_period: usize,
+    /// (time, amount) of the pending replenishments of a sporadic server
+    replenishments: Vec<(usize, usize)>,
+    /// when a sporadic server became active and the budget it consumed since
+    activation: Option<(usize, usize)>,
+    /// (time, budget left) at every change of the budget
+    pub(crate) budget_log: Vec<(usize, usize)>,
+    pub(crate) queue: VecDeque<Job>,
+    /// metadata of the server task
+    pub(crate) metadata: Rc<Metadata>,
+}
+
+impl Server {
+    pub(crate) fn new(id: usize, kind: ServerKind, budget: usize, period: usize) -> Self {
+        let capacity = match kind {
+            ServerKind::Sporadic => budget,
+            _ => 0,
+        };
+        Self {
+            id,
+            kind,
+            budget,
+            period,
+            priority: usize::MAX,
+            capacity,
+            deadline: match kind {
+                ServerKind::Cbs => 0,
+                _ => period,
+            },
+            next_period: 0,
+            replenishments: Vec::new(),
+            activation: None,
+            budget_log: vec![(0, capacity)],
+            queue: VecDeque::new(),
+            metadata: Rc::default(),
+        }
+    }
+
+    pub(crate) fn with_priority(self, priority: usize) -> Self {
+        Self { priority, ..self }
+    }
+
+    pub(crate) fn with_metadata(self, metadata: Rc<Metadata>) -> Self {
+        Self { metadata, ..self }
+    }
+
+    pub(crate) fn kind(&self) -> ServerKind {
+        self.kind
+    }
+
+    fn log(&mut self, now: usize) {
+        // only the last change at `now` counts
+        if self.budget_log.last().is_some_and(|last| last.0 == now) {
+            self.budget_log.pop();
+        }
+        match self.budget_log.last() {
+            Some(last) if last.1 == self.capacity => {}
+            _ => self.budget_log.push((now, self.capacity)),
+        }
+    }
+
+    /// queues a request arriving at `now`
+    pub(crate) fn arrive(&mut self, request: Job, now: usize) {
+        if self.kind == ServerKind::Cbs {
+            // an idle server keeps its deadline only if the budget left
+            // can't exceed its bandwidth until then
+            if self.queue.is_empty()
+                && self.capacity * self.period >= self.deadline.saturating_sub(now) * self.budget
+            {
+                self.capacity = self.budget;
+                self.deadline = now + self.period;
+                self.log(now);
+            }
+        }
+        self.queue.push_back(request);
+        self.activate(now);
+    }
+
+    /// starts an activation of a sporadic server that got a request to serve
+    /// and the budget for it at `now`
+    fn activate(&mut self, now: usize) {
+        if self.kind == ServerKind::Sporadic && self.activation.is_none() && self.is_active() {
+            self.activation = Some((now, 0));
+        }
+    }
+
+    /// applies the replenishments due by `now`
+    pub(crate) fn update(&mut self, now: usize) {
+        match self.kind {
+            ServerKind::Cbs => {}
+            ServerKind::Polling | ServerKind::Deferrable => {
+                while self.next_period <= now {
+                    self.capacity = self.budget;
+                    self.deadline = self.next_period + self.period;
+                    self.next_period += self.period;
+                }
+                if self.kind == ServerKind::Polling && self.queue.is_empty() {
+                    self.capacity = 0;
+                }
+            }
+            ServerKind::Sporadic => {
+                let (due, pending): (Vec<_>, Vec<_>) =
+                    self.replenishments.iter().partition(|(t, _)| *t <= now);
+                self.replenishments = pending;
+                self.capacity += due.iter().map(|(_, amount)| amount).sum::<usize>();
+                self.activate(now);
+            }
+        }
+        self.log(now);
+    }
+
+    /// start of the next period of polling and deferrable servers,
+    /// where they get their budget back
+    pub(crate) fn next_period(&self) -> Option<usize> {
+        match self.kind {
+            ServerKind::Polling | ServerKind::Deferrable => Some(self.next_period),
+            ServerKind::Cbs | ServerKind::Sporadic => None,
+        }
+    }
+
+    pub(crate) fn utilization(&self) -> f64 {
+        self.budget as f64 / self.period as f64
+    }
+
+    /// whether the server has a request to serve and the budget for it
+    pub(crate) fn is_active(&self) -> bool {
+        !self.queue.is_empty() && self.capacity > 0
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// the current activation of the server as a job, for the scheduling policy
+    pub(crate) fn proxy(&self) -> Job {
+        Job::new(
+            self.id,
+            0,
+            self.deadline.saturating_sub(self.period),
+            self.capacity,
+            self.deadline,
+        )
+        .with_period(self.period)
+        .with_priority(self.priority)
+    }
+
+    /// charges `duration` of execution of a request, that ended at `now`, to the budget.
+    /// returns the time of the replenishment of a sporadic server it schedules, if any
+    pub(crate) fn consume(&mut self, duration: usize, now: usize) -> Option<usize> {
+        self.capacity -= duration;
+        let mut replenishment_time = None;
+        match self.kind {
+            ServerKind::Cbs | ServerKind::Deferrable => {}
+            ServerKind::Polling => {
+                if self.queue.is_empty() {
+                    self.capacity = 0;
+                }
+            }
+            ServerKind::Sporadic => {
+                // the activation starts with the request, not when it gets to run
+                let (activation, consumed) = self.activation.get_or_insert((now - duration, 0));
+                *consumed += duration;
+                let replenishment = (*activation + self.period, *consumed);
+                self.deadline = replenishment.0;
+                if self.capacity == 0 || self.queue.is_empty() {
+                    self.replenishments.push(replenishment);
+                    self.activation = None;
+                    replenishment_time = Some(replenishment.0);
+                }
+            }
+        }
+        self.log(now);
+        replenishment_time
+    }
+
+    /// reacts to the budget running out at `now`
+    pub(crate) fn exhaust(&mut self, now: usize) {
+        if self.kind == ServerKind::Cbs {
+            self.capacity = self.budget;
+            self.deadline += self.period;
+            self.log(now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(id: usize, arrival: usize) -> Job {
+        Job::new(id, 0, arrival, 1, usize::MAX)
+    }
+
+    #[test]
+    fn cbs_deadline_postponement() {
+        let mut server = Server::new(1, ServerKind::Cbs, 2, 5);
+        server.arrive(request(10, 3), 3);
+        assert_eq!((server.capacity, server.deadline), (2, 8));
+
+        server.consume(2, 5);
+        assert_eq!((server.capacity, server.deadline), (0, 8));
+        server.exhaust(5);
+        assert_eq!((server.capacity, server.deadline), (2, 13));
+
+        // arriving while the server is busy keeps its deadline
+        server.arrive(request(11, 6), 6);
+        assert_eq!((server.capacity, server.deadline), (2, 13));
+
+        // an idle server with too much budget left for its deadline is recharged
+        server.queue.clear();
+        server.arrive(request(12, 10), 10);
+        assert_eq!((server.capacity, server.deadline), (2, 15));
+
+        // otherwise it goes on with what's left
+        server.queue.clear();
+        server.consume(1, 11);
+        server.arrive(request(13, 12), 12);
+        assert_eq!((server.capacity, server.deadline), (1, 15));
+    }
+
+    #[test]
+    fn periodic_replenishment() {
+        let mut polling = Server::new(1, ServerKind::Polling, 2, 5);
+        let mut deferrable = Server::new(2, ServerKind::Deferrable, 2, 5);
+        for server in [&mut polling, &mut deferrable] {
+            server.update(0);
+            server.arrive(request(10, 1), 1);
+            server.update(1);
+        }
+        // the polling server found no request at the start of the period
+        assert_eq!((polling.capacity, deferrable.capacity), (0, 2));
+        for server in [&mut polling, &mut deferrable] {
+            server.update(5);
+        }
+        assert_eq!((polling.capacity, deferrable.capacity), (2, 2));
+        assert_eq!(polling.next_period(), Some(10));
+    }
+
+    #[test]
+    fn sporadic_replenishment() {
+        let mut server = Server::new(1, ServerKind::Sporadic, 3, 10);
+        server.arrive(request(10, 2), 2);
+        server.arrive(request(11, 2), 2);
+        server.queue.pop_front();
+        server.consume(1, 3);
+        server.queue.pop_front();
+        assert_eq!(server.consume(1, 5), Some(12));
+        // one replenishment for the whole activation
+        assert_eq!(server.replenishments, vec![(12, 2)]);
+        assert_eq!(server.capacity, 1);
+
+        server.update(12);
+        assert_eq!(server.capacity, 3);
+        assert_eq!(server.budget_log, vec![(0, 3), (3, 2), (5, 1), (12, 3)]);
+    }
+
+    #[test]
+    fn sporadic_replenishment_from_activation() {
+        let mut server = Server::new(1, ServerKind::Sporadic, 3, 10);
+        // the request arrives at 2 but only runs from 5 to 7
+        server.arrive(request(10, 2), 2);
+        server.queue.pop_front();
+        assert_eq!(server.consume(2, 7), Some(12));
+
+        // a request left without budget activates the server on the replenishment
+        let mut server = Server::new(1, ServerKind::Sporadic, 1, 10);
+        server.arrive(request(10, 0), 0);
+        server.arrive(request(11, 0), 0);
+        server.queue.pop_front();
+        assert_eq!(server.consume(1, 1), Some(10));
+        server.update(10);
+        server.queue.pop_front();
+        assert_eq!(server.consume(1, 14), Some(20));
+    }
+}
@@ -1,96 +1,729 @@
 use std::collections::BinaryHeap;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::ops::Neg;
+use std::rc::Rc;
 
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use serde::Serialize;
+
+use crate::execution::ExecutionTime;
+use crate::job::CriticalSection;
 use crate::job::Job;
 use crate::job::JobList;
+use crate::job::Preemption;
+use crate::metadata::Metadata;
+use crate::platform::{Platform, Speed};
+use crate::priority::{Admission, ResponseTimeAnalysis, SchedulabilityTest};
+use crate::server::{Server, ServerKind};
+use crate::time::{Resolution, Time, TimeError};
+
+#[derive(Clone, Debug)]
+enum TaskKind {
+    /// jobs are released exactly every `period`
+    Periodic,
+    /// jobs are released at least `period` apart,
+    /// plus a uniformly distributed extra delay in `0..=max_delay`
+    Sporadic { max_delay: usize },
+    /// releases no jobs, it serves aperiodic jobs with a budget of `wcet` every `period`
+    Server(ServerKind),
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Criticality {
+    Lo,
+    Hi,
+}
 
 #[derive(Clone, Debug)]
 pub struct Task {
     id: usize,
     wcet: usize,
+    /// wcet in HI mode, only set for HI tasks
+    hi_wcet: Option<usize>,
     period: usize,
+    deadline: usize,
+    offset: usize,
+    kind: TaskKind,
+    execution: ExecutionTime,
+    critical_sections: Vec<CriticalSection>,
+    /// longest execution between two preemption points in limited-preemptive mode
+    non_preemptive_region: Option<usize>,
+    /// fixed priority of the task, 0 being the highest
+    priority: Option<usize>,
+    /// (index, count) of the piece, for a piece of a task split across processors
+    piece: Option<(usize, usize)>,
+    metadata: Metadata,
+    seed: u64,
 }
 
 impl Task {
+    /// creates a task with an implicit deadline (deadline == period)
     pub fn new(id: usize, wcet: usize, period: usize) -> Self {
-        Self { id, wcet, period }
+        Self {
+            id,
+            wcet,
+            hi_wcet: None,
+            period,
+            deadline: period,
+            offset: 0,
+            kind: TaskKind::Periodic,
+            execution: ExecutionTime::Wcet,
+            critical_sections: Vec::new(),
+            non_preemptive_region: None,
+            priority: None,
+            piece: None,
+            metadata: Metadata::default(),
+            seed: id as u64,
+        }
+    }
+
+    /// creates a task with an implicit deadline from times in real units.
+    /// fails if they are not whole numbers of ticks of `resolution`
+    pub fn from_time(
+        id: usize,
+        wcet: Time,
+        period: Time,
+        resolution: &Resolution,
+    ) -> Result<Self, TimeError> {
+        Ok(Self::new(
+            id,
+            resolution.ticks(wcet)?,
+            resolution.ticks(period)?,
+        ))
+    }
+
+    /// creates a sporadic task with an implicit deadline.
+    /// consecutive jobs are released `min_interarrival` plus a random delay
+    /// of at most `max_delay` apart
+    pub fn sporadic(id: usize, wcet: usize, min_interarrival: usize, max_delay: usize) -> Self {
+        Self {
+            kind: TaskKind::Sporadic { max_delay },
+            ..Self::new(id, wcet, min_interarrival)
+        }
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// wcet of the task, its LO budget for HI tasks
+    pub fn wcet(&self) -> usize {
+        self.wcet
+    }
+
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    pub fn deadline(&self) -> usize {
+        self.deadline
+    }
+
+    pub fn priority(&self) -> Option<usize> {
+        self.priority
+    }
+
+    /// sets the fixed priority of the task, 0 being the highest.
+    /// tasks without one run at the lowest priority under `FixedPriority`
+    pub fn with_priority(self, priority: usize) -> Self {
+        Self {
+            priority: Some(priority),
+            ..self
+        }
+    }
+
+    /// creates a server of `budget` every `period` for the aperiodic jobs
+    /// pointing at `id`, see `AperiodicJob::with_server`.
+    /// the partitioners treat it as a task of utilization budget / period
+    pub fn server(id: usize, kind: ServerKind, budget: usize, period: usize) -> Self {
+        Self {
+            kind: TaskKind::Server(kind),
+            ..Self::new(id, budget, period)
+        }
+    }
+
+    /// creates a server from times in real units, see `Task::server`.
+    /// fails if they are not whole numbers of ticks of `resolution`
+    pub fn server_from_time(
+        id: usize,
+        kind: ServerKind,
+        budget: Time,
+        period: Time,
+        resolution: &Resolution,
+    ) -> Result<Self, TimeError> {
+        Ok(Self::server(
+            id,
+            kind,
+            resolution.ticks(budget)?,
+            resolution.ticks(period)?,
+        ))
+    }
+
+    /// sets a human readable name, reported next to the id of every job
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.metadata.name = Some(name.into());
+        self
+    }
+
+    /// adds a free-form label such as a subsystem, see `Selector`
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.metadata.labels.push(label.into());
+        self
+    }
+
+    /// sets a user-defined attribute, see `Selector`
+    pub fn with_attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.attributes.insert(key.into(), value.into());
+        self
+    }
+
+    /// sets the seed of the random generator used for this task.
+    /// defaults to the id of the task, so replicas of a task always
+    /// observe the same job releases
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self { seed, ..self }
+    }
+
+    /// sets the distribution the actual execution time of each job is drawn from.
+    /// jobs run for exactly `wcet` by default
+    pub fn with_execution_time(self, execution: ExecutionTime) -> Self {
+        execution.validate(self.max_wcet());
+        Self { execution, ..self }
+    }
+
+    /// makes the task HI criticality, `wcet` becomes its LO budget
+    /// and `hi_wcet` its budget in HI mode
+    pub fn with_hi_wcet(self, hi_wcet: usize) -> Self {
+        assert!(self.wcet <= hi_wcet, "HI wcet is smaller than LO wcet");
+        Self {
+            hi_wcet: Some(hi_wcet),
+            ..self
+        }
+    }
+
+    pub fn criticality(&self) -> Criticality {
+        match self.hi_wcet {
+            Some(_) => Criticality::Hi,
+            None => Criticality::Lo,
+        }
+    }
+
+    fn max_wcet(&self) -> usize {
+        self.hi_wcet.unwrap_or(self.wcet)
+    }
+
+    /// declares that each job holds `resource` exclusively for `length` units of
+    /// its execution, after having executed for `offset` units.
+    /// resources are local to a processor and shared under the Stack Resource Policy
+    pub fn with_critical_section(mut self, resource: usize, offset: usize, length: usize) -> Self {
+        assert!(
+            offset + length <= self.max_wcet(),
+            "critical section is longer than the wcet"
+        );
+        self.critical_sections.push(CriticalSection {
+            resource,
+            offset,
+            length,
+        });
+        self
+    }
+
+    /// `with_critical_section` in real units of time
+    pub fn with_critical_section_time(
+        self,
+        resource: usize,
+        offset: Time,
+        length: Time,
+        resolution: &Resolution,
+    ) -> Result<Self, TimeError> {
+        Ok(self.with_critical_section(
+            resource,
+            resolution.ticks(offset)?,
+            resolution.ticks(length)?,
+        ))
+    }
+
+    /// jobs can only be preempted after every `length` units of execution
+    /// when scheduled in limited-preemptive mode
+    pub fn with_non_preemptive_region(self, length: usize) -> Self {
+        assert!(length > 0, "empty non-preemptive region");
+        Self {
+            non_preemptive_region: Some(length),
+            ..self
+        }
+    }
+
+    /// `with_non_preemptive_region` in real units of time
+    pub fn with_non_preemptive_region_time(
+        self,
+        length: Time,
+        resolution: &Resolution,
+    ) -> Result<Self, TimeError> {
+        Ok(self.with_non_preemptive_region(resolution.ticks(length)?))
+    }
+
+    /// longest interval the task can block a job with a shorter deadline for
+    fn blocking(&self, preemption: Preemption) -> usize {
+        match preemption {
+            Preemption::Preemptive => 0,
+            Preemption::NonPreemptive => self.max_wcet(),
+            Preemption::Limited => self
+                .non_preemptive_region
+                .map_or(0, |q| q.min(self.max_wcet())),
+        }
+    }
+
+    /// sets the relative deadline of the task.
+    /// it can be shorter (constrained) or longer (arbitrary) than the period
+    pub fn with_deadline(self, deadline: usize) -> Self {
+        Self { deadline, ..self }
+    }
+
+    /// `with_deadline` in real units of time
+    pub fn with_deadline_time(
+        self,
+        deadline: Time,
+        resolution: &Resolution,
+    ) -> Result<Self, TimeError> {
+        Ok(self.with_deadline(resolution.ticks(deadline)?))
+    }
+
+    /// sets the release time of the first job (phase) of the task
+    pub fn with_offset(self, offset: usize) -> Self {
+        Self { offset, ..self }
+    }
+
+    /// `with_offset` in real units of time
+    pub fn with_offset_time(
+        self,
+        offset: Time,
+        resolution: &Resolution,
+    ) -> Result<Self, TimeError> {
+        Ok(self.with_offset(resolution.ticks(offset)?))
     }
 
     pub(crate) fn jobs_till(&self, deadline: usize) -> JobList {
-        let mut now = 0_usize;
+        let mut rng = stream_rng(self.seed, Stream::Release);
+        // separate stream so execution times don't shift sporadic releases
+        let mut execution_rng = stream_rng(self.seed, Stream::Execution);
+        let metadata = Rc::new(self.metadata.clone());
+        let mut now = self.offset;
         let mut iteration = 0;
         let mut jobs = JobList::new();
+        if let TaskKind::Server(kind) = self.kind {
+            jobs.push_server(
+                Server::new(self.id, kind, self.wcet, self.period)
+                    .with_priority(self.priority.unwrap_or(usize::MAX))
+                    .with_metadata(metadata),
+            );
+            return jobs;
+        }
         while now < deadline {
-            let deadline = now + self.period;
-            jobs.push(Job::new(self.id, iteration, now, self.wcet, deadline));
+            let deadline = now + self.deadline;
+            let execution = self.execution.sample(self.max_wcet(), &mut execution_rng);
+            jobs.push(
+                Job::new(self.id, iteration, now, self.wcet, deadline)
+                    .with_period(self.period)
+                    .with_priority(self.priority.unwrap_or(usize::MAX))
+                    .with_execution(execution)
+                    .with_criticality(self.criticality())
+                    .with_critical_sections(self.critical_sections.clone())
+                    .with_non_preemptive_region(self.non_preemptive_region)
+                    .with_piece(self.piece)
+                    .with_metadata(metadata.clone()),
+            );
             iteration += 1;
-            now += self.period;
+            now += match self.kind {
+                TaskKind::Periodic => self.period,
+                TaskKind::Sporadic { max_delay } => self.period + rng.gen_range(0..=max_delay),
+                TaskKind::Server(_) => unreachable!(),
+            };
         }
         jobs
     }
     pub fn utilization(&self) -> f32 {
         self.wcet as f32 / self.period as f32
     }
+
+    /// the part of the task that runs for `wcet` within `window` units
+    /// from `start` after every release, for splitting the task across processors.
+    /// pieces always run for their full budget
+    fn piece(&self, wcet: usize, start: usize, window: usize) -> Self {
+        Self {
+            wcet,
+            deadline: window,
+            offset: self.offset + start,
+            execution: ExecutionTime::Wcet,
+            ..self.clone()
+        }
+    }
+
+    /// the task with its execution times on a processor running at `speed`
+    fn scaled(&self, speed: Speed) -> Self {
+        Self {
+            wcet: speed.time(self.wcet),
+            hi_wcet: self.hi_wcet.map(|wcet| speed.time(wcet)),
+            non_preemptive_region: self.non_preemptive_region.map(|q| speed.time(q)),
+            ..self.clone()
+        }
+    }
+
+    /// wcet / min(deadline, period).
+    /// equals the utilization for implicit and arbitrary deadlines
+    pub fn density(&self) -> f32 {
+        self.wcet as f32 / self.deadline.min(self.period) as f32
+    }
+}
+
+/// the random draws of a task, each from its own generator
+#[derive(Clone, Copy)]
+enum Stream {
+    Release = 0,
+    Execution = 1,
+}
+
+/// generator of `stream` for a task seeded with `seed`.
+/// the seed and the stream make distinct halves of the key of the generator,
+/// so no two (seed, stream) pairs share a generator
+fn stream_rng(seed: u64, stream: Stream) -> StdRng {
+    let mut key = [0; 32];
+    key[..8].copy_from_slice(&seed.to_le_bytes());
+    key[8..16].copy_from_slice(&(stream as u64).to_le_bytes());
+    StdRng::from_seed(key)
 }
 
 enum ProcessorError {
     TaskAlreadyExists(Task),
     NotEnoughCapacity(Task),
 }
-struct Processor {
+
+/// sufficient EDF test accounting for the blocking by non-preemptive regions:
+/// for every task k, the density of the tasks with a deadline no longer than
+/// D_k plus B_k / D_k must not exceed 1, where B_k is the longest
+/// non-preemptive region of the tasks with a longer deadline
+fn edf_with_blocking<'a>(tasks: impl Iterator<Item = &'a Task>, preemption: Preemption) -> bool {
+    let mut tasks: Vec<&Task> = tasks.collect();
+    tasks.sort_by_key(|t| t.deadline.min(t.period));
+    let mut density = 0.0;
+    for (k, task) in tasks.iter().enumerate() {
+        let deadline = task.deadline.min(task.period);
+        density += task.density();
+        let blocking = tasks[k + 1..]
+            .iter()
+            .filter(|t| t.deadline.min(t.period) > deadline)
+            .map(|t| t.blocking(preemption))
+            .max()
+            .unwrap_or(0);
+        if density + blocking as f32 / deadline as f32 > 1.0 {
+            return false;
+        }
+    }
+    true
+}
+
+/// processor demand criterion for EDF: for every absolute deadline L up to a bound,
+/// the demand of the jobs released and due in [0, L] plus the blocking by
+/// jobs with a longer deadline must not exceed L.
+/// offsets are ignored, which makes it exact for synchronous task sets
+/// and sufficient otherwise
+fn edf_demand<'a>(tasks: impl Iterator<Item = &'a Task>, preemption: Preemption) -> bool {
+    let tasks: Vec<&Task> = tasks.collect();
+    let utilization: f64 = tasks.iter().map(|t| t.wcet as f64 / t.period as f64).sum();
+    if utilization > 1.0 {
+        return false;
+    }
+    let max_deadline = tasks.iter().map(|t| t.deadline).max().unwrap_or(0);
+    let hyperperiod = tasks.iter().map(|t| t.period).fold(1, num::integer::lcm);
+    let mut bound = hyperperiod + max_deadline;
+    if utilization < 1.0 {
+        let busy: f64 = tasks
+            .iter()
+            .map(|t| t.period.saturating_sub(t.deadline) as f64 * t.wcet as f64 / t.period as f64)
+            .sum::<f64>()
+            / (1.0 - utilization);
+        bound = bound.min((busy.ceil() as usize).max(max_deadline));
+    }
+
+    let mut checkpoints = BTreeSet::new();
+    for task in &tasks {
+        checkpoints.extend((task.deadline..=bound).step_by(task.period));
+    }
+    checkpoints.into_iter().all(|l| {
+        let demand: usize = tasks
+            .iter()
+            .filter(|t| t.deadline <= l)
+            .map(|t| ((l - t.deadline) / t.period + 1) * t.wcet)
+            .sum();
+        let blocking = tasks
+            .iter()
+            .filter(|t| t.deadline > l)
+            .map(|t| t.blocking(preemption))
+            .max()
+            .unwrap_or(0);
+        demand + blocking <= l
+    })
+}
+
+/// response time analysis of every task, preempted by the tasks
+/// with the same or a higher `priority`, the smaller value being the higher
+fn fixed_priority_rta<'a>(
+    tasks: impl Iterator<Item = &'a Task>,
+    priority: impl Fn(&Task) -> usize,
+) -> bool {
+    let tasks: Vec<&Task> = tasks.collect();
+    tasks.iter().all(|task| {
+        let higher_priority: Vec<&Task> = tasks
+            .iter()
+            .filter(|t| t.id != task.id && priority(t) <= priority(task))
+            .copied()
+            .collect();
+        ResponseTimeAnalysis.is_schedulable(task, &higher_priority)
+    })
+}
+
+/// sufficient FIFO test: a job completes by the end of the busy period it's
+/// released in, so the synchronous busy period, the longest one, must fit in
+/// the shortest deadline
+fn fifo_busy_period<'a>(tasks: impl Iterator<Item = &'a Task>) -> bool {
+    let tasks: Vec<&Task> = tasks.collect();
+    let bound = tasks
+        .iter()
+        .map(|t| t.deadline.min(t.period))
+        .min()
+        .unwrap_or(0);
+    let mut busy: usize = tasks.iter().map(|t| t.wcet).sum();
+    loop {
+        if busy > bound {
+            return false;
+        }
+        let next = tasks.iter().map(|t| busy.div_ceil(t.period) * t.wcet).sum();
+        if next == busy {
+            return true;
+        }
+        busy = next;
+    }
+}
+
+struct Processor<'a> {
     tasks: Vec<Task>,
+    /// the tasks with their execution times on this processor
+    scaled: Vec<Task>,
+    /// 1 - the utilization of the scaled tasks, whatever the admission test
     capacity: f32,
     task_ids: HashSet<usize>,
+    preemption: Preemption,
+    admission: Admission,
+    platform: &'a Platform,
+    index: usize,
 }
 
-impl Processor {
-    fn new() -> Self {
+impl<'a> Processor<'a> {
+    fn new(
+        preemption: Preemption,
+        admission: Admission,
+        platform: &'a Platform,
+        index: usize,
+    ) -> Self {
         Self {
             tasks: Vec::new(),
+            scaled: Vec::new(),
             capacity: 1.0,
             task_ids: HashSet::new(),
+            preemption,
+            admission,
+            platform,
+            index,
         }
     }
+
+    fn scale(&self, task: &Task) -> Task {
+        task.scaled(self.platform.speed(task.id, self.index))
+    }
+
+    /// a processor for each of the processors of `platform`
+    fn all(preemption: Preemption, admission: Admission, platform: &'a Platform) -> Vec<Self> {
+        (0..platform.num_proc())
+            .map(|index| Self::new(preemption, admission, platform, index))
+            .collect()
+    }
+
+    /// whether the processor passes its admission test with `scaled` on top of its tasks
+    fn admits(&self, scaled: &Task) -> bool {
+        let tasks = self.scaled.iter().chain([scaled]);
+        match self.admission {
+            Admission::Edf => edf_with_blocking(tasks, self.preemption),
+            Admission::RateMonotonic => fixed_priority_rta(tasks, |t| t.period),
+            Admission::DeadlineMonotonic => fixed_priority_rta(tasks, |t| t.deadline),
+            Admission::FixedPriority => TaskList::from(tasks.cloned().collect::<Vec<Task>>())
+                .assign_priorities(&ResponseTimeAnalysis)
+                .is_ok(),
+            Admission::Fifo => fifo_busy_period(tasks),
+        }
+    }
+    // the task is handed back on failure, so it can be tried on another processor
+    #[allow(clippy::result_large_err)]
     fn push(&mut self, task: Task) -> Result<(), ProcessorError> {
+        let scaled = self.scale(&task);
+        if self.task_ids.contains(&task.id) {
+            Err(ProcessorError::TaskAlreadyExists(task))
+        } else if self.capacity < scaled.utilization() || !self.admits(&scaled) {
+            Err(ProcessorError::NotEnoughCapacity(task))
+        } else {
+            self.capacity -= scaled.utilization();
+            self.task_ids.insert(task.id);
+            self.tasks.push(task);
+            self.scaled.push(scaled);
+            Ok(())
+        }
+    }
+    /// pushes `task` if the processor passes the processor demand criterion with it
+    #[allow(clippy::result_large_err)]
+    fn push_exact(&mut self, task: Task) -> Result<(), ProcessorError> {
+        let scaled = self.scale(&task);
         if self.task_ids.contains(&task.id) {
             Err(ProcessorError::TaskAlreadyExists(task))
-        } else if self.capacity < task.utilization() {
+        } else if !edf_demand(self.scaled.iter().chain([&scaled]), self.preemption) {
             Err(ProcessorError::NotEnoughCapacity(task))
         } else {
-            self.capacity -= task.utilization();
+            self.capacity -= scaled.utilization();
             self.task_ids.insert(task.id);
             self.tasks.push(task);
+            self.scaled.push(scaled);
             Ok(())
         }
     }
+
     fn take(self) -> TaskList {
+        let mut tasks = self.tasks;
+        if self.admission == Admission::FixedPriority {
+            // the priorities are found with the execution times on this processor
+            let scaled = TaskList::from(self.scaled).assign_priorities(&ResponseTimeAnalysis);
+            if let Ok(scaled) = scaled {
+                for (task, scaled) in tasks.iter_mut().zip(scaled.tasks) {
+                    task.priority = scaled.priority;
+                }
+            }
+        }
         TaskList {
-            tasks: self.tasks,
+            tasks,
             replication: 0,
+            preemption: self.preemption,
+            admission: self.admission,
         }
     }
+
+    /// the task lists of `processors`, in the order of the platform
+    /// unless its processors are interchangeable
+    fn take_all(processors: impl Iterator<Item = Self>) -> Vec<TaskList> {
+        let mut processors: Vec<Self> = processors.collect();
+        if processors
+            .first()
+            .is_some_and(|p| !p.platform.is_identical())
+        {
+            processors.sort_by_key(|p| p.index);
+        }
+        processors.into_iter().map(Self::take).collect()
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct TaskList {
     tasks: Vec<Task>,
     replication: usize,
+    preemption: Preemption,
+    admission: Admission,
 }
 
 impl TaskList {
     pub fn new() -> Self {
-        Self {
-            tasks: Vec::new(),
-            replication: 0,
-        }
+        Self::default()
     }
     pub fn with_replication(self, replication: usize) -> Self {
         Self {
-            tasks: self.tasks,
             replication,
+            ..self
+        }
+    }
+
+    pub fn replication(&self) -> usize {
+        self.replication
+    }
+
+    /// preemption mode the tasks are scheduled in.
+    /// the partitioners account for the blocking of non-preemptive regions
+    pub fn with_preemption(self, preemption: Preemption) -> Self {
+        Self { preemption, ..self }
+    }
+
+    /// test the partitioners admit the tasks on a processor with,
+    /// after the policy the processors schedule their jobs with
+    pub fn with_admission(self, admission: Admission) -> Self {
+        Self { admission, ..self }
+    }
+
+    /// whether the tasks pass the EDF density test, accounting for the blocking
+    /// of non-preemptive regions in the preemption mode of the list
+    pub fn is_edf_schedulable(&self) -> bool {
+        edf_with_blocking(self.tasks.iter(), self.preemption)
+    }
+
+    /// whether every task passes `test` under preemptive fixed-priority
+    /// scheduling with the priorities of the tasks.
+    /// tasks without a priority are considered to have the lowest one
+    pub fn is_fixed_priority_schedulable(&self, test: &impl SchedulabilityTest) -> bool {
+        let priority = |t: &Task| t.priority.unwrap_or(usize::MAX);
+        self.tasks.iter().all(|task| {
+            let higher_priority: Vec<&Task> = self
+                .tasks
+                .iter()
+                .filter(|t| t.id != task.id && priority(t) <= priority(task))
+                .collect();
+            test.is_schedulable(task, &higher_priority)
+        })
+    }
+
+    /// assigns a priority to every task with Audsley's Optimal Priority Assignment,
+    /// 0 being the highest. from the lowest level up, each level goes to
+    /// the first unassigned task that passes `test` with all the other
+    /// unassigned tasks having higher priorities.
+    /// the list is handed back untouched if no feasible ordering exists
+    pub fn assign_priorities(mut self, test: &impl SchedulabilityTest) -> Result<Self, Self> {
+        let mut unassigned: Vec<usize> = (0..self.tasks.len()).collect();
+        let mut priorities = vec![0; self.tasks.len()];
+        for level in (0..self.tasks.len()).rev() {
+            let found = unassigned.iter().position(|&i| {
+                let higher_priority: Vec<&Task> = unassigned
+                    .iter()
+                    .filter(|&&j| j != i)
+                    .map(|&j| &self.tasks[j])
+                    .collect();
+                test.is_schedulable(&self.tasks[i], &higher_priority)
+            });
+            match found {
+                Some(position) => priorities[unassigned.remove(position)] = level,
+                None => return Err(self),
+            }
         }
+        for (task, priority) in self.tasks.iter_mut().zip(priorities) {
+            task.priority = Some(priority);
+        }
+        Ok(self)
     }
+
+    /// Goossens-Funk-Baruah density test for global EDF on `num_proc` processors:
+    /// total density <= m - (m - 1) * max density, counting every replica.
+    /// it's sufficient, `schedule_global` can still succeed when it fails
+    pub fn is_global_edf_schedulable(&self, num_proc: usize) -> bool {
+        let replicas = (self.replication + 1) as f32;
+        let density: f32 = self.tasks.iter().map(|t| t.density()).sum::<f32>() * replicas;
+        let max_density = self.tasks.iter().map(|t| t.density()).fold(0.0, f32::max);
+        let m = num_proc as f32;
+        self.replication < num_proc && density <= m - (m - 1.0) * max_density
+    }
+
     pub fn hyperperiod(&self) -> usize {
         self.tasks
             .iter()
@@ -98,12 +731,29 @@ impl TaskList {
             .fold(1, num::integer::lcm)
     }
 
+    /// length of the interval that has to be simulated to cover every
+    /// pattern of the schedule.
+    /// it is the hyperperiod for synchronous task sets and
+    /// max(offset) + 2 * hyperperiod for asynchronous ones
+    pub fn feasibility_interval(&self) -> usize {
+        let max_offset = self.tasks.iter().map(|t| t.offset).max().unwrap_or(0);
+        if max_offset == 0 {
+            self.hyperperiod()
+        } else {
+            max_offset + 2 * self.hyperperiod()
+        }
+    }
+
+    /// jobs released in the feasibility interval of the task set
     pub fn jobs_till_hyperperiod(&self) -> JobList {
-        let hyperperiod = self.hyperperiod();
+        self.jobs_till(self.feasibility_interval())
+    }
 
+    /// jobs released before `to` by the tasks of the set
+    pub fn jobs_till(&self, to: usize) -> JobList {
         let mut joblist = JobList::new();
         for task in &self.tasks {
-            joblist.join(task.jobs_till(hyperperiod));
+            joblist.join(task.jobs_till(to));
         }
         joblist
     }
@@ -112,12 +762,43 @@ impl TaskList {
         self.tasks.push(task)
     }
 
-    pub fn first_fit(&self, num_proc: usize) -> Result<Vec<TaskList>, Vec<TaskList>> {
-        let mut processors = Vec::with_capacity(num_proc);
+    pub fn contains(&self, id: usize) -> bool {
+        self.tasks.iter().any(|t| t.id == id)
+    }
+
+    /// scaling factor of the relative deadlines of HI tasks in LO mode of EDF-VD.
+    /// it's 1 when plain EDF already guarantees the HI mode and
+    /// `None` when the task set fails the EDF-VD schedulability test.
+    /// densities are used in place of utilizations for constrained deadlines
+    pub fn edf_vd_factor(&self) -> Option<f32> {
+        let density =
+            |wcet: usize, task: &Task| wcet as f32 / task.deadline.min(task.period) as f32;
+        let (hi_tasks, lo_tasks): (Vec<&Task>, Vec<&Task>) = self
+            .tasks
+            .iter()
+            .partition(|t| t.criticality() == Criticality::Hi);
+        let lo_lo: f32 = lo_tasks.iter().map(|t| t.density()).sum();
+        let hi_lo: f32 = hi_tasks.iter().map(|t| t.density()).sum();
+        let hi_hi: f32 = hi_tasks.iter().map(|t| density(t.max_wcet(), t)).sum();
 
-        for _ in 0..num_proc {
-            processors.push(Processor::new());
+        if lo_lo + hi_hi <= 1.0 {
+            Some(1.0)
+        } else if lo_lo + hi_lo > 1.0 {
+            None
+        } else {
+            let factor = hi_lo / (1.0 - lo_lo);
+            (factor * lo_lo + hi_hi <= 1.0).then_some(factor)
         }
+    }
+
+    pub fn first_fit(&self, num_proc: usize) -> Result<Vec<TaskList>, Vec<TaskList>> {
+        self.first_fit_on(&Platform::identical(num_proc))
+    }
+
+    /// first fit on the processors of `platform`, by the utilizations
+    /// of the tasks scaled to each processor
+    pub fn first_fit_on(&self, platform: &Platform) -> Result<Vec<TaskList>, Vec<TaskList>> {
+        let mut processors = Processor::all(self.preemption, self.admission, platform);
 
         for task in &self.tasks {
             for _ in 0..self.replication + 1 {
@@ -142,30 +823,35 @@ impl TaskList {
     }
 
     pub fn worst_fit(&self, num_proc: usize) -> Result<Vec<TaskList>, Vec<TaskList>> {
-        struct ProcWrapper(Processor);
-        impl PartialEq for ProcWrapper {
+        self.worst_fit_on(&Platform::identical(num_proc))
+    }
+
+    /// worst fit on the processors of `platform`, by the utilizations
+    /// of the tasks scaled to each processor
+    pub fn worst_fit_on(&self, platform: &Platform) -> Result<Vec<TaskList>, Vec<TaskList>> {
+        struct ProcWrapper<'a>(Processor<'a>);
+        impl PartialEq for ProcWrapper<'_> {
             fn eq(&self, other: &Self) -> bool {
                 self.0.capacity == other.0.capacity
             }
         }
-        impl Eq for ProcWrapper {
-            fn assert_receiver_is_total_eq(&self) {}
-        }
-        impl Ord for ProcWrapper {
+        impl Eq for ProcWrapper<'_> {}
+        impl Ord for ProcWrapper<'_> {
             fn cmp(&self, other: &Self) -> std::cmp::Ordering {
                 self.0.capacity.neg().total_cmp(&other.0.capacity.neg())
             }
         }
-        impl PartialOrd for ProcWrapper {
+        impl PartialOrd for ProcWrapper<'_> {
             fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-                self.0.capacity.neg().partial_cmp(&other.0.capacity.neg())
+                Some(self.cmp(other))
             }
         }
 
-        let mut processors = BinaryHeap::with_capacity(num_proc);
-        for _ in 0..num_proc {
-            processors.push(ProcWrapper(Processor::new()));
-        }
+        let mut processors: BinaryHeap<ProcWrapper> =
+            Processor::all(self.preemption, self.admission, platform)
+                .into_iter()
+                .map(ProcWrapper)
+                .collect();
 
         for task in &self.tasks {
             for _ in 0..self.replication + 1 {
@@ -189,46 +875,43 @@ impl TaskList {
                     }
                 }
                 if processors.is_empty() {
-                    return Err(processors
-                        .into_vec()
-                        .into_iter()
-                        .map(|w| w.0.take())
-                        .collect());
+                    return Err(Processor::take_all(processors.into_iter().map(|w| w.0)));
                 }
             }
         }
-        Ok(processors
-            .into_vec()
-            .into_iter()
-            .map(|w| w.0.take())
-            .collect())
+        Ok(Processor::take_all(processors.into_iter().map(|w| w.0)))
     }
 
     pub fn best_fit(&self, num_proc: usize) -> Result<Vec<TaskList>, Vec<TaskList>> {
-        struct ProcWrapper(Processor);
-        impl PartialEq for ProcWrapper {
+        self.best_fit_on(&Platform::identical(num_proc))
+    }
+
+    /// best fit on the processors of `platform`, by the utilizations
+    /// of the tasks scaled to each processor
+    pub fn best_fit_on(&self, platform: &Platform) -> Result<Vec<TaskList>, Vec<TaskList>> {
+        struct ProcWrapper<'a>(Processor<'a>);
+        impl PartialEq for ProcWrapper<'_> {
             fn eq(&self, other: &Self) -> bool {
                 self.0.capacity == other.0.capacity
             }
         }
-        impl Eq for ProcWrapper {
-            fn assert_receiver_is_total_eq(&self) {}
-        }
-        impl Ord for ProcWrapper {
+        impl Eq for ProcWrapper<'_> {}
+        impl Ord for ProcWrapper<'_> {
             fn cmp(&self, other: &Self) -> std::cmp::Ordering {
                 self.0.capacity.total_cmp(&other.0.capacity)
             }
         }
-        impl PartialOrd for ProcWrapper {
+        impl PartialOrd for ProcWrapper<'_> {
             fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-                self.0.capacity.partial_cmp(&other.0.capacity)
+                Some(self.cmp(other))
             }
         }
 
-        let mut processors = BinaryHeap::with_capacity(num_proc);
-        for _ in 0..num_proc {
-            processors.push(ProcWrapper(Processor::new()));
-        }
+        let mut processors: BinaryHeap<ProcWrapper> =
+            Processor::all(self.preemption, self.admission, platform)
+                .into_iter()
+                .map(ProcWrapper)
+                .collect();
 
         for task in &self.tasks {
             for _ in 0..self.replication + 1 {
@@ -255,19 +938,111 @@ impl TaskList {
                     }
                 }
                 if !pushed {
-                    return Err(processors
-                        .into_vec()
-                        .into_iter()
-                        .map(|w| w.0.take())
-                        .collect());
+                    return Err(Processor::take_all(processors.into_iter().map(|w| w.0)));
                 }
             }
         }
-        Ok(processors
-            .into_vec()
-            .into_iter()
-            .map(|w| w.0.take())
-            .collect())
+        Ok(Processor::take_all(processors.into_iter().map(|w| w.0)))
+    }
+}
+
+impl TaskList {
+    /// semi-partitioned EDF for task sets the partitioners fail on.
+    /// tasks are placed first fit under the exact processor demand criterion.
+    /// a task (or replica) that doesn't fit on any processor is split with C=D:
+    /// every processor in turn takes the largest piece that runs with zero laxity
+    /// (its window equals its budget) right after the previous piece,
+    /// until the rest of the task fits on a processor as a whole.
+    /// pieces are released at fixed points after every release of the task,
+    /// so they migrate at known points, and are labelled with a `piece` attribute.
+    /// a piece only runs once the previous one is done when the processors are
+    /// simulated together, see `schedule_dags` and `split_reports`.
+    /// the tasks are always placed for EDF, whatever the admission test of the list
+    pub fn semi_partitioned(&self, num_proc: usize) -> Result<Vec<TaskList>, Vec<TaskList>> {
+        self.semi_partitioned_on(&Platform::identical(num_proc))
+    }
+
+    /// semi-partitioned EDF on the processors of `platform`.
+    /// a piece runs with zero laxity on its processor, at the speed of the processor
+    pub fn semi_partitioned_on(&self, platform: &Platform) -> Result<Vec<TaskList>, Vec<TaskList>> {
+        let mut processors = Processor::all(self.preemption, self.admission, platform);
+
+        for task in &self.tasks {
+            for _ in 0..self.replication + 1 {
+                // the task is handed back by every processor it doesn't fit on
+                let rejected =
+                    processors.iter_mut().try_fold(task.clone(), |task, proc| {
+                        match proc.push_exact(task) {
+                            Ok(_) => None,
+                            Err(ProcessorError::NotEnoughCapacity(t)) => Some(t),
+                            Err(ProcessorError::TaskAlreadyExists(t)) => Some(t),
+                        }
+                    });
+                if let Some(task) = rejected {
+                    if !Self::split(&mut processors, &task) {
+                        return Err(processors.into_iter().map(|p| p.take()).collect());
+                    }
+                }
+            }
+        }
+        Ok(processors.into_iter().map(|p| p.take()).collect())
+    }
+
+    /// splits `task` with C=D over `processors`, leaving them untouched on failure
+    fn split(processors: &mut [Processor], task: &Task) -> bool {
+        // the budgets of HI tasks, servers and critical sections can't be cut
+        if task.hi_wcet.is_some()
+            || matches!(task.kind, TaskKind::Server(_))
+            || !task.critical_sections.is_empty()
+        {
+            return false;
+        }
+        let deadline = task.deadline.min(task.period);
+        let mut pieces: Vec<(usize, Task)> = Vec::new();
+        // execution of the task placed so far, and the time it takes
+        let mut placed = 0;
+        let mut start = 0;
+        for (i, proc) in processors.iter().enumerate() {
+            if proc.task_ids.contains(&task.id) {
+                continue;
+            }
+            if start >= deadline {
+                break;
+            }
+            let speed = proc.platform.speed(task.id, proc.index);
+            let fits = |piece: &Task| {
+                edf_demand(
+                    proc.scaled.iter().chain([&piece.scaled(speed)]),
+                    proc.preemption,
+                )
+            };
+            let remaining = task.wcet - placed;
+            let last = task.piece(remaining, start, deadline - start);
+            if fits(&last) {
+                pieces.push((i, last));
+                let count = pieces.len();
+                for (index, (i, piece)) in pieces.into_iter().enumerate() {
+                    let piece = Task {
+                        piece: Some((index, count)),
+                        ..piece
+                    }
+                    .with_attribute("piece", format!("{}/{}", index + 1, count));
+                    let pushed = processors[i].push_exact(piece).is_ok();
+                    debug_assert!(pushed);
+                }
+                return true;
+            }
+            if let Some(piece) = (1..remaining)
+                .rev()
+                .map(|wcet| task.piece(wcet, start, speed.time(wcet)))
+                .find(|piece| fits(piece))
+            {
+                placed += piece.wcet;
+                start += piece.deadline;
+                pieces.push((i, piece));
+            }
+        }
+        false
     }
 }
 
@@ -275,7 +1050,7 @@ impl From<Vec<Task>> for TaskList {
     fn from(tasks: Vec<Task>) -> Self {
         Self {
             tasks,
-            replication: 0,
+            ..Self::default()
         }
     }
 }
@@ -283,6 +1058,7 @@ impl From<Vec<Task>> for TaskList {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{Platform, ResponseTimeAnalysis};
 
     #[test]
     fn split_tasks() {
@@ -313,6 +1089,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::redundant_pattern_matching)]
     fn num_proc_lt_replication() {
         let t1 = Task::new(1, 2, 6);
         let t2 = Task::new(2, 2, 8);
@@ -324,6 +1101,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::redundant_pattern_matching)]
     fn total_util_gt_num_proc() {
         let t1 = Task::new(1, 8, 10);
         let t2 = Task::new(2, 6, 10);
@@ -335,6 +1113,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::redundant_pattern_matching)]
     fn total_util_le_num_proc() {
         let t1 = Task::new(1, 4, 10);
         let t2 = Task::new(2, 3, 10);
@@ -396,4 +1175,250 @@ mod tests {
         assert_eq!(ids[2], vec![3, 4]);
         assert_eq!(ids[3], vec![3, 4]);
     }
+
+    #[test]
+    fn constrained_deadline_density() {
+        // total utilization is 0.6 but the total density is 1.2
+        let t1 = Task::new(1, 3, 10).with_deadline(5);
+        let t2 = Task::new(2, 3, 10).with_deadline(5);
+        let tasklist = TaskList::from(vec![t1, t2]);
+        assert!(tasklist.first_fit(1).is_err());
+        assert!(tasklist.worst_fit(1).is_err());
+        assert!(tasklist.best_fit(1).is_err());
+        assert!(tasklist.first_fit(2).is_ok());
+    }
+
+    #[test]
+    fn feasibility_interval() {
+        let t1 = Task::new(1, 2, 6);
+        let t2 = Task::new(2, 2, 8);
+        let tasklist = TaskList::from(vec![t1.clone(), t2.clone()]);
+        assert_eq!(tasklist.feasibility_interval(), 24);
+
+        let tasklist = TaskList::from(vec![t1, t2.with_offset(5)]);
+        assert_eq!(tasklist.feasibility_interval(), 53);
+    }
+
+    #[test]
+    fn edf_vd_factor() {
+        let hi = Task::new(1, 2, 10).with_hi_wcet(6);
+        let lo = Task::new(2, 3, 10);
+        let tasklist = TaskList::from(vec![hi.clone(), lo]);
+        assert_eq!(tasklist.edf_vd_factor(), Some(1.0));
+
+        let lo = Task::new(2, 5, 10);
+        let tasklist = TaskList::from(vec![hi.clone(), lo]);
+        assert!(tasklist.edf_vd_factor().unwrap() < 1.0);
+
+        let lo = Task::new(2, 8, 10);
+        let tasklist = TaskList::from(vec![hi, lo]);
+        assert_eq!(tasklist.edf_vd_factor(), None);
+    }
+
+    #[test]
+    fn non_preemptive_blocking() {
+        let t1 = Task::new(1, 1, 2);
+        let t2 = Task::new(2, 3, 10);
+        let tasklist = TaskList::from(vec![t1, t2.clone()]);
+        assert!(tasklist.is_edf_schedulable());
+        assert!(tasklist.first_fit(1).is_ok());
+
+        let tasklist = tasklist.with_preemption(Preemption::NonPreemptive);
+        assert!(!tasklist.is_edf_schedulable());
+        assert!(tasklist.first_fit(1).is_err());
+        assert!(tasklist.worst_fit(1).is_err());
+        assert!(tasklist.best_fit(1).is_err());
+        assert!(tasklist.first_fit(2).is_ok());
+
+        let t1 = Task::new(1, 1, 2);
+        let t2 = t2.with_non_preemptive_region(1);
+        let tasklist = TaskList::from(vec![t1, t2]).with_preemption(Preemption::Limited);
+        assert!(tasklist.is_edf_schedulable());
+    }
+
+    #[test]
+    fn optimal_priority_assignment() {
+        // t2 and t3 miss their deadlines at the lowest priority
+        let t1 = Task::new(1, 3, 10);
+        let t2 = Task::new(2, 2, 12).with_deadline(4);
+        let t3 = Task::new(3, 1, 5);
+        let tasklist = TaskList::from(vec![t1, t2, t3]);
+        assert!(!tasklist.is_fixed_priority_schedulable(&ResponseTimeAnalysis));
+
+        let tasklist = tasklist.assign_priorities(&ResponseTimeAnalysis).unwrap();
+        assert!(tasklist.is_fixed_priority_schedulable(&ResponseTimeAnalysis));
+        assert_eq!(
+            tasklist
+                .tasks
+                .iter()
+                .map(|t| t.priority)
+                .collect::<Vec<_>>(),
+            vec![Some(2), Some(1), Some(0)]
+        );
+
+        let t1 = Task::new(1, 3, 4);
+        let t2 = Task::new(2, 2, 5);
+        assert!(TaskList::from(vec![t1, t2])
+            .assign_priorities(&ResponseTimeAnalysis)
+            .is_err());
+    }
+
+    #[test]
+    fn admission() {
+        // schedulable under EDF, but t2 misses its deadline under any fixed priorities
+        let t1 = Task::new(1, 2, 5);
+        let t2 = Task::new(2, 4, 7);
+        let tasklist = TaskList::from(vec![t1, t2]);
+        let ids = |tasklists: Vec<TaskList>| {
+            tasklists
+                .iter()
+                .map(|tasks| tasks.tasks.iter().map(|t| t.id).collect::<Vec<usize>>())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            ids(tasklist.first_fit(2).unwrap()),
+            vec![vec![1, 2], vec![]]
+        );
+        for admission in [
+            Admission::RateMonotonic,
+            Admission::DeadlineMonotonic,
+            Admission::FixedPriority,
+            Admission::Fifo,
+        ] {
+            let tasklist = TaskList::from(tasklist.tasks.clone()).with_admission(admission);
+            assert_eq!(ids(tasklist.first_fit(2).unwrap()), vec![vec![1], vec![2]]);
+            assert!(tasklist.first_fit(1).is_err());
+        }
+
+        // the tasks of every processor get their priorities
+        let t3 = Task::new(3, 1, 4);
+        let tasklists = TaskList::from(vec![t3, Task::new(1, 2, 5)])
+            .with_admission(Admission::FixedPriority)
+            .first_fit(1)
+            .unwrap();
+        assert!(tasklists[0].is_fixed_priority_schedulable(&ResponseTimeAnalysis));
+    }
+
+    #[test]
+    fn semi_partitioned() {
+        let t1 = Task::new(1, 6, 10);
+        let t2 = Task::new(2, 6, 10);
+        let t3 = Task::new(3, 6, 10);
+        let tasklist = TaskList::from(vec![t1.clone(), t2.clone(), t3.clone()]);
+        assert!(tasklist.first_fit(2).is_err());
+
+        let processors = tasklist.semi_partitioned(2).unwrap();
+        let pieces: Vec<Vec<(usize, usize, usize, usize)>> = processors
+            .iter()
+            .map(|p| {
+                p.tasks
+                    .iter()
+                    .map(|t| (t.id, t.wcet, t.offset, t.deadline))
+                    .collect()
+            })
+            .collect();
+        // C=D piece of 4 on the first processor, the rest in the remaining window
+        assert_eq!(
+            pieces,
+            vec![
+                vec![(1, 6, 0, 10), (3, 4, 0, 4)],
+                vec![(2, 6, 0, 10), (3, 2, 4, 6)]
+            ]
+        );
+
+        let timelines: Vec<Vec<(usize, usize)>> = processors
+            .iter()
+            .map(|p| {
+                let mut jobs = JobList::new();
+                for task in &p.tasks {
+                    jobs.join(task.jobs_till(30));
+                }
+                jobs.schedule();
+                jobs.timeline(30)[..10].to_vec()
+            })
+            .collect();
+        assert_eq!(
+            timelines[0],
+            [[(3, 0); 4].as_slice(), &[(1, 0); 6]].concat()
+        );
+        assert_eq!(
+            timelines[1],
+            [[(2, 0); 6].as_slice(), &[(3, 0); 2], &[(0, 0); 2]].concat()
+        );
+
+        // not even splitting can exceed the capacity of the processors
+        let t4 = Task::new(4, 3, 10);
+        let tasklist = TaskList::from(vec![t1, t2, t3, t4]);
+        assert!(tasklist.semi_partitioned(2).is_err());
+    }
+
+    #[test]
+    fn heterogeneous() {
+        // the replica only fits on a core as fast as the reference one
+        let t1 = Task::new(1, 6, 10);
+        let tasklist = TaskList::from(vec![t1.clone()]).with_replication(1);
+        let big_little = Platform::uniform(vec![1.0, 0.5]);
+        assert!(tasklist.first_fit(2).is_ok());
+        assert!(tasklist.first_fit_on(&big_little).is_err());
+        assert!(tasklist.best_fit_on(&big_little).is_err());
+        assert!(tasklist.worst_fit_on(&big_little).is_err());
+        let table = big_little.clone().with_wcets(&t1, vec![6, 8]);
+        assert!(tasklist.first_fit_on(&table).is_ok());
+
+        // t2 takes 12 on the little core, C=D puts 4 of it on the big one
+        // and the remaining 2 in the 6 left on the little one
+        let t2 = Task::new(2, 6, 10);
+        let tasklist = TaskList::from(vec![t1, t2]);
+        let processors = tasklist.semi_partitioned_on(&big_little).unwrap();
+        let pieces: Vec<Vec<(usize, usize, usize, usize)>> = processors
+            .iter()
+            .map(|p| {
+                p.tasks
+                    .iter()
+                    .map(|t| (t.id, t.wcet, t.offset, t.deadline))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            pieces,
+            vec![vec![(1, 6, 0, 10), (2, 4, 0, 4)], vec![(2, 2, 4, 6)]]
+        );
+    }
+
+    #[test]
+    fn parameters_in_time() {
+        let resolution = Resolution::new(Time::us(250));
+        let task = Task::from_time(1, Time::us(750), Time::ms(2), &resolution)
+            .and_then(|t| t.with_deadline_time(Time::ms(1), &resolution))
+            .and_then(|t| t.with_offset_time(Time::us(500), &resolution))
+            .and_then(|t| {
+                t.with_critical_section_time(1, Time::us(250), Time::us(500), &resolution)
+            })
+            .and_then(|t| t.with_non_preemptive_region_time(Time::us(500), &resolution))
+            .unwrap();
+        assert_eq!(
+            (task.wcet, task.period, task.deadline, task.offset),
+            (3, 8, 4, 2)
+        );
+        assert_eq!(task.critical_sections[0].length, 2);
+        assert_eq!(task.non_preemptive_region, Some(2));
+        assert!(task.with_offset_time(Time::us(100), &resolution).is_err());
+
+        let server =
+            Task::server_from_time(2, ServerKind::Cbs, Time::ms(1), Time::ms(4), &resolution)
+                .unwrap();
+        assert_eq!((server.wcet, server.period), (4, 16));
+    }
+
+    #[test]
+    fn independent_streams() {
+        // the execution stream of a task is not the release stream of the next seed
+        let draws = |seed, stream| {
+            let mut rng = stream_rng(seed, stream);
+            (0..4).map(|_| rng.gen::<u64>()).collect::<Vec<_>>()
+        };
+        assert_ne!(draws(1, Stream::Execution), draws(2, Stream::Release));
+        assert_ne!(draws(1, Stream::Execution), draws(1, Stream::Release));
+        assert_eq!(draws(1, Stream::Execution), draws(1, Stream::Execution));
+    }
 }
@@ -0,0 +1,166 @@
+use std::fmt;
+
+use serde::Serialize;
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimeUnit {
+    Ns,
+    Us,
+    Ms,
+}
+
+impl TimeUnit {
+    fn nanos(self) -> u64 {
+        match self {
+            TimeUnit::Ns => 1,
+            TimeUnit::Us => 1_000,
+            TimeUnit::Ms => 1_000_000,
+        }
+    }
+}
+
+/// a duration stated in one of the supported units
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Time {
+    value: u64,
+    unit: TimeUnit,
+}
+
+impl Time {
+    pub fn new(value: u64, unit: TimeUnit) -> Self {
+        Self { value, unit }
+    }
+    pub fn ns(value: u64) -> Self {
+        Self::new(value, TimeUnit::Ns)
+    }
+    pub fn us(value: u64) -> Self {
+        Self::new(value, TimeUnit::Us)
+    }
+    pub fn ms(value: u64) -> Self {
+        Self::new(value, TimeUnit::Ms)
+    }
+    pub fn unit(&self) -> TimeUnit {
+        self.unit
+    }
+    /// the time in ns, an error if that doesn't fit in a u64
+    fn nanos(&self) -> Result<u64, TimeError> {
+        self.value
+            .checked_mul(self.unit.nanos())
+            .ok_or(TimeError::Overflow { time: *self })
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = match self.unit {
+            TimeUnit::Ns => "ns",
+            TimeUnit::Us => "us",
+            TimeUnit::Ms => "ms",
+        };
+        write!(f, "{}{}", self.value, unit)
+    }
+}
+
+#[derive(Debug)]
+pub enum TimeError {
+    /// `time` is not a whole number of ticks
+    Inexact { time: Time, tick: Time },
+    /// `time` is too long to be stated in ns
+    Overflow { time: Time },
+}
+
+impl fmt::Display for TimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeError::Inexact { time, tick } => {
+                write!(f, "{time} is not a multiple of the {tick} resolution")
+            }
+            TimeError::Overflow { time } => write!(f, "{time} overflows the ns range"),
+        }
+    }
+}
+
+impl std::error::Error for TimeError {}
+
+/// length of one simulation tick.
+/// the simulator only deals with whole ticks, a resolution converts
+/// from and to real time units
+#[derive(Clone, Copy, Debug)]
+pub struct Resolution {
+    tick: Time,
+}
+
+impl Resolution {
+    pub fn new(tick: Time) -> Self {
+        assert!(tick.value > 0, "empty tick");
+        Self { tick }
+    }
+
+    /// the coarsest resolution every one of `times` converts to exactly
+    pub fn exact_for(times: &[Time]) -> Result<Self, TimeError> {
+        let nanos = times
+            .iter()
+            .map(Time::nanos)
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .fold(0, num::integer::gcd)
+            .max(1);
+        Ok(Self::new(Time::ns(nanos)))
+    }
+
+    /// number of ticks in `time`, an error if it's not a whole number
+    pub fn ticks(&self, time: Time) -> Result<usize, TimeError> {
+        let tick = self.tick.nanos()?;
+        let nanos = time.nanos()?;
+        if !nanos.is_multiple_of(tick) {
+            return Err(TimeError::Inexact {
+                time,
+                tick: self.tick,
+            });
+        }
+        Ok((nanos / tick) as usize)
+    }
+
+    /// number of `unit`s in a tick, an error if it's not a whole number
+    pub fn per_tick(&self, unit: TimeUnit) -> Result<usize, TimeError> {
+        Resolution::new(Time::new(1, unit)).ticks(self.tick)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_conversion() {
+        let resolution = Resolution::new(Time::us(250));
+        assert_eq!(resolution.ticks(Time::ms(10)).unwrap(), 40);
+        assert_eq!(resolution.ticks(Time::us(500)).unwrap(), 2);
+        assert!(matches!(
+            resolution.ticks(Time::us(300)),
+            Err(TimeError::Inexact { .. })
+        ));
+        assert_eq!(resolution.per_tick(TimeUnit::Us).unwrap(), 250);
+        assert!(resolution.per_tick(TimeUnit::Ms).is_err());
+    }
+
+    #[test]
+    fn exact_for() {
+        let times = [Time::us(300), Time::ms(10), Time::us(1250)];
+        let resolution = Resolution::exact_for(&times).unwrap();
+        assert_eq!(resolution.per_tick(TimeUnit::Us).unwrap(), 50);
+        for time in times {
+            assert!(resolution.ticks(time).is_ok());
+        }
+    }
+    #[test]
+    fn overflow() {
+        let resolution = Resolution::new(Time::us(1));
+        assert!(matches!(
+            resolution.ticks(Time::ms(u64::MAX)),
+            Err(TimeError::Overflow { .. })
+        ));
+        assert!(Resolution::exact_for(&[Time::us(1), Time::ms(u64::MAX / 10)]).is_err());
+    }
+}